@@ -0,0 +1,133 @@
+//! Helpers shared by the derives that rebuild an item field by field.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::visit::{self, Visit};
+use syn::visit_mut::{self, VisitMut};
+use syn::{DeriveInput, Error, Field, Fields, GenericParam, Generics, Ident, Lifetime, Type};
+
+/// The lifetime parameters of an item.
+pub struct Lifetimes(Vec<Ident>);
+
+impl Lifetimes {
+    pub fn of(generics: &Generics) -> Self {
+        Self(
+            generics
+                .lifetimes()
+                .map(|l| l.lifetime.ident.clone())
+                .collect(),
+        )
+    }
+
+    /// Returns whether `ty` mentions any of the lifetimes.
+    pub fn mentioned_by(&self, ty: &Type) -> bool {
+        struct Finder<'a>(&'a [Ident], bool);
+
+        impl<'ast> Visit<'ast> for Finder<'_> {
+            fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+                self.1 |= self.0.contains(&lifetime.ident);
+                visit::visit_lifetime(self, lifetime);
+            }
+        }
+
+        let mut finder = Finder(&self.0, false);
+        finder.visit_type(ty);
+        finder.1
+    }
+
+    /// Returns `ty` with every one of the lifetimes replaced by `with`.
+    pub fn replace(&self, ty: &Type, with: &Lifetime) -> Type {
+        struct Replacer<'a>(&'a [Ident], &'a Lifetime);
+
+        impl VisitMut for Replacer<'_> {
+            fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+                if self.0.contains(&lifetime.ident) {
+                    *lifetime = self.1.clone();
+                }
+
+                visit_mut::visit_lifetime_mut(self, lifetime);
+            }
+        }
+
+        let mut ty = ty.clone();
+        Replacer(&self.0, with).visit_type_mut(&mut ty);
+        ty
+    }
+
+    /// Returns the type of the item with every lifetime replaced by `with`.
+    pub fn replace_in_self(&self, input: &DeriveInput, with: &Lifetime) -> TokenStream {
+        let name = &input.ident;
+        if input.generics.params.is_empty() {
+            return quote!(#name);
+        }
+
+        let args = input.generics.params.iter().map(|param| match param {
+            GenericParam::Lifetime(..) => quote!(#with),
+            GenericParam::Type(param) => param.ident.to_token_stream(),
+            GenericParam::Const(param) => param.ident.to_token_stream(),
+        });
+
+        quote!(#name<#(#args),*>)
+    }
+}
+
+/// Returns whether the field is marked `#[flex(clone)]`, rejecting any
+/// other option and cloning a field that borrows with the item's lifetimes.
+pub fn cloned(field: &Field, lifetimes: &Lifetimes) -> syn::Result<bool> {
+    let mut clone = false;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("flex")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("clone") {
+                clone = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `clone`"))
+            }
+        })?;
+    }
+
+    if clone && lifetimes.mentioned_by(&field.ty) {
+        let msg = "`#[flex(clone)]` fields cannot borrow with the item's lifetimes";
+        return Err(Error::new_spanned(&field.ty, msg));
+    }
+
+    Ok(clone)
+}
+
+/// Builds a pattern that destructures `path` and an expression that
+/// rebuilds it, passing each field and its binding through `convert`.
+pub fn rebuild(
+    path: TokenStream,
+    fields: &Fields,
+    convert: &mut impl FnMut(&Field, TokenStream) -> syn::Result<TokenStream>,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    match fields {
+        Fields::Named(named) => {
+            let idents: Vec<_> = named.named.iter().map(|f| &f.ident).collect();
+            let values = named.named.iter().map(|f| {
+                let ident = &f.ident;
+                convert(f, quote!(#ident))
+            });
+
+            let values = values.collect::<syn::Result<Vec<_>>>()?;
+            let pat = quote!(#path { #(#idents),* });
+            let expr = quote!(#path { #(#idents: #values),* });
+            Ok((pat, expr))
+        }
+
+        Fields::Unnamed(unnamed) => {
+            let bindings: Vec<_> = (0..unnamed.unnamed.len())
+                .map(|i| format_ident!("__{}", i))
+                .collect();
+
+            let values = unnamed.unnamed.iter().zip(&bindings);
+            let values = values.map(|(f, b)| convert(f, quote!(#b)));
+            let values = values.collect::<syn::Result<Vec<_>>>()?;
+            let pat = quote!(#path(#(#bindings),*));
+            let expr = quote!(#path(#(#values),*));
+            Ok((pat, expr))
+        }
+
+        Fields::Unit => Ok((path.clone(), path)),
+    }
+}
@@ -0,0 +1,243 @@
+//! Procedural macros for the [`flex`](https://docs.rs/flex) crate.
+//!
+//! These macros are re-exported by `flex` behind its `macros` feature and
+//! should be used through it.
+
+mod fields;
+mod owned;
+mod reborrow;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{parse_macro_input, parse_quote, Attribute, DeriveInput, Error, ItemTrait};
+use syn::{LitStr, Path, TraitItem};
+
+/// Parses a `crate = "path"` argument into `path`.
+fn parse_crate(meta: &ParseNestedMeta, path: &mut Path) -> syn::Result<()> {
+    if meta.path.is_ident("crate") {
+        *path = meta.value()?.parse::<LitStr>()?.parse()?;
+        Ok(())
+    } else {
+        Err(meta.error("expected `crate`"))
+    }
+}
+
+/// Returns the path of the `flex` crate, as overridden by a
+/// `#[flex(crate = "path")]` attribute on the item.
+fn crate_path(attrs: &[Attribute]) -> syn::Result<Path> {
+    let mut path = parse_quote!(::flex);
+    for attr in attrs.iter().filter(|a| a.path().is_ident("flex")) {
+        attr.parse_nested_meta(|meta| parse_crate(&meta, &mut path))?;
+    }
+
+    Ok(path)
+}
+
+/// Makes `Flex<'a, dyn Trait>` claimable and cloneable.
+///
+/// This adds `flex::CloneBox` as a supertrait of the annotated trait and
+/// implements `From<&dyn Trait>` and `Clone` for `Box<dyn Trait>`, including
+/// the `Send` and `Sync` variants of the trait object. Every implementor of
+/// the trait must therefore be `Clone`.
+///
+/// Traits with associated types are not supported, since their trait
+/// objects cannot be named without fixing the associated types.
+///
+/// The generated code refers to the `flex` crate as `::flex`. If it is
+/// renamed in `Cargo.toml`, pass its name with `crate = "..."`, as in
+/// `#[my_flex::claimable(crate = "my_flex")]`.
+///
+/// # Examples
+///
+/// ```
+/// use flex::Flex;
+///
+/// #[flex::claimable]
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// #[derive(Clone)]
+/// struct Square(f64);
+///
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// let square = Square(2.0);
+/// let borrowed: Flex<dyn Shape> = Flex::Lend(&square);
+/// let owned: Flex<'static, dyn Shape> = borrowed.claim();
+/// assert_eq!(owned.clone().area(), 4.0);
+/// ```
+#[proc_macro_attribute]
+pub fn claimable(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut krate = parse_quote!(::flex);
+    let parser = syn::meta::parser(|meta| parse_crate(&meta, &mut krate));
+    parse_macro_input!(attr with parser);
+
+    let mut item = parse_macro_input!(item as ItemTrait);
+    match expand(&mut item, &krate) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(item: &mut ItemTrait, krate: &Path) -> syn::Result<TokenStream2> {
+    for trait_item in &item.items {
+        if let TraitItem::Type(ty) = trait_item {
+            let msg = "`claimable` traits cannot have associated types";
+            return Err(Error::new_spanned(ty, msg));
+        }
+    }
+
+    item.colon_token.get_or_insert_with(Default::default);
+    item.supertraits.push(parse_quote!(#krate::CloneBox));
+
+    let ident = &item.ident;
+    let (_, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    let mut generics = item.generics.clone();
+    generics.params.insert(0, parse_quote!('__flex));
+    let (impl_generics, _, _) = generics.split_for_impl();
+
+    let bounds = [
+        quote!(),
+        quote!(+ ::core::marker::Send),
+        quote!(+ ::core::marker::Sync),
+        quote!(+ ::core::marker::Send + ::core::marker::Sync),
+    ];
+
+    let impls = bounds.iter().map(|bounds| {
+        let object = quote!(dyn #ident #ty_generics #bounds + '__flex);
+
+        quote! {
+            impl #impl_generics ::core::convert::From<&(#object)>
+                for #krate::__private::Box<#object> #where_clause
+            {
+                fn from(value: &(#object)) -> Self {
+                    #krate::clone_box(value)
+                }
+            }
+
+            impl #impl_generics ::core::clone::Clone
+                for #krate::__private::Box<#object> #where_clause
+            {
+                fn clone(&self) -> Self {
+                    #krate::clone_box(&**self)
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #item
+        #(#impls)*
+    })
+}
+
+/// Derives `flex::IntoOwned` for a struct or enum.
+///
+/// Fields whose types mention one of the item's lifetimes are converted
+/// with `IntoOwned::into_owned`; all other fields are moved as they are.
+/// The resulting `Owned` type is the same item with every lifetime
+/// parameter replaced by `'static`.
+///
+/// If the `flex` crate is renamed in `Cargo.toml`, name it with
+/// `#[flex(crate = "...")]` on the item.
+///
+/// # Examples
+///
+/// ```
+/// use flex::{Flex, IntoOwned};
+///
+/// #[derive(IntoOwned)]
+/// struct Message<'a> {
+///     id: u32,
+///     name: Flex<'a, str>,
+///     tags: Vec<Flex<'a, str>>,
+/// }
+///
+/// let name = String::from("hello");
+/// let message = Message { id: 1, name: Flex::from(name.as_str()), tags: Vec::new() };
+/// let owned: Message<'static> = message.into_owned();
+/// ```
+///
+/// Fields accept the `#[flex(clone)]` of the `Reborrow` derive, which does
+/// not change how they are converted, and reject any other option:
+///
+/// ```compile_fail
+/// use flex::{Flex, IntoOwned};
+///
+/// #[derive(IntoOwned)]
+/// struct Message<'a> {
+///     #[flex(copy)]
+///     name: Flex<'a, str>,
+/// }
+/// ```
+#[proc_macro_derive(IntoOwned, attributes(flex))]
+pub fn derive_into_owned(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match owned::expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derives `flex::Reborrow` for a struct or enum.
+///
+/// Fields whose types mention one of the item's lifetimes are borrowed with
+/// `Reborrow::reborrow`, so every `Flex` becomes a `Lend` pointing into the
+/// original. The resulting `Reborrowed` type is the same item with every
+/// lifetime parameter replaced by the lifetime of the borrow.
+///
+/// All other fields are copied, so that a view never allocates, and must
+/// be `Copy`. A field that should be cloned instead, such as a `String`,
+/// must opt in with `#[flex(clone)]`.
+///
+/// If the `flex` crate is renamed in `Cargo.toml`, name it with
+/// `#[flex(crate = "...")]` on the item.
+///
+/// # Examples
+///
+/// ```
+/// use flex::{Flex, Reborrow};
+///
+/// #[derive(Reborrow)]
+/// struct Message<'a> {
+///     id: u32,
+///     name: Flex<'a, str>,
+///     #[flex(clone)]
+///     label: String,
+/// }
+///
+/// let owned: Message<'static> = Message {
+///     id: 1,
+///     name: Flex::Give("hello".into()),
+///     label: String::from("greeting"),
+/// };
+/// let view: Message<'_> = owned.reborrow();
+/// ```
+///
+/// A field that is neither `Copy` nor marked `#[flex(clone)]` is rejected:
+///
+/// ```compile_fail
+/// use flex::{Flex, Reborrow};
+///
+/// #[derive(Reborrow)]
+/// struct Message<'a> {
+///     name: Flex<'a, str>,
+///     label: String,
+/// }
+/// ```
+#[proc_macro_derive(Reborrow, attributes(flex))]
+pub fn derive_reborrow(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match reborrow::expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,75 @@
+//! The `IntoOwned` derive.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Error, Field, Lifetime};
+
+use crate::fields::{self, Lifetimes};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let krate = crate::crate_path(&input.attrs)?;
+    let lifetimes = Lifetimes::of(&input.generics);
+    let name = &input.ident;
+    let static_lifetime = Lifetime::new("'static", Span::call_site());
+    let owned = lifetimes.replace_in_self(input, &static_lifetime);
+
+    let mut generics = input.generics.clone();
+    let mut convert = |field: &Field, value: TokenStream| {
+        // `#[flex(clone)]` only affects `Reborrow`, as other fields are moved.
+        fields::cloned(field, &lifetimes)?;
+
+        let ty = &field.ty;
+        if !lifetimes.mentioned_by(ty) {
+            return Ok(value);
+        }
+
+        let static_ty = lifetimes.replace(ty, &static_lifetime);
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ty: #krate::IntoOwned<Owned = #static_ty>));
+
+        Ok(quote!(#krate::IntoOwned::into_owned(#value)))
+    };
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pat, expr) = fields::rebuild(quote!(#name), &data.fields, &mut convert)?;
+            quote!(match self { #pat => #expr })
+        }
+
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                if let Some(attr) = variant.attrs.iter().find(|a| a.path().is_ident("flex")) {
+                    let msg = "`#[flex(...)]` is not supported on variants";
+                    return Err(Error::new_spanned(attr, msg));
+                }
+
+                let ident = &variant.ident;
+                let path = quote!(#name::#ident);
+                let (pat, expr) = fields::rebuild(path, &variant.fields, &mut convert)?;
+                Ok(quote!(#pat => #expr,))
+            });
+
+            let arms = arms.collect::<syn::Result<Vec<_>>>()?;
+            quote!(match self { #(#arms)* })
+        }
+
+        Data::Union(data) => {
+            let msg = "`IntoOwned` cannot be derived for unions";
+            return Err(Error::new_spanned(data.union_token, msg));
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #krate::IntoOwned for #name #ty_generics #where_clause {
+            type Owned = #owned;
+
+            fn into_owned(self) -> Self::Owned {
+                #body
+            }
+        }
+    })
+}
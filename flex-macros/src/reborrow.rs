@@ -0,0 +1,92 @@
+//! The `Reborrow` derive.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_quote, parse_quote_spanned, Data, DeriveInput, Error, Field, Lifetime};
+
+use crate::fields::{self, Lifetimes};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let krate = crate::crate_path(&input.attrs)?;
+    let lifetimes = Lifetimes::of(&input.generics);
+    let name = &input.ident;
+    let view = Lifetime::new("'__flex", Span::call_site());
+    let reborrowed = lifetimes.replace_in_self(input, &view);
+
+    let mut generics = input.generics.clone();
+    for lifetime in input.generics.lifetimes() {
+        let lifetime = &lifetime.lifetime;
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#lifetime: #view));
+    }
+
+    let mut convert = |field: &Field, value: TokenStream| {
+        let ty = &field.ty;
+        if fields::cloned(field, &lifetimes)? {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: ::core::clone::Clone));
+
+            return Ok(quote!(::core::clone::Clone::clone(#value)));
+        }
+
+        if !lifetimes.mentioned_by(ty) {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote_spanned!(ty.span()=> #ty: ::core::marker::Copy));
+
+            return Ok(quote_spanned!(ty.span()=> *#value));
+        }
+
+        let view_ty = lifetimes.replace(ty, &view);
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ty: #krate::Reborrow<#view, Reborrowed = #view_ty>));
+
+        Ok(quote!(#krate::Reborrow::reborrow(#value)))
+    };
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pat, expr) = fields::rebuild(quote!(#name), &data.fields, &mut convert)?;
+            quote!(match self { #pat => #expr })
+        }
+
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let path = quote!(#name::#ident);
+                let (pat, expr) = fields::rebuild(path, &variant.fields, &mut convert)?;
+                Ok(quote!(#pat => #expr,))
+            });
+
+            let arms = arms.collect::<syn::Result<Vec<_>>>()?;
+            quote!(match self { #(#arms)* })
+        }
+
+        Data::Union(data) => {
+            let msg = "`Reborrow` cannot be derived for unions";
+            return Err(Error::new_spanned(data.union_token, msg));
+        }
+    };
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    generics.params.insert(0, parse_quote!(#view));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #krate::Reborrow<#view> for #name #ty_generics #where_clause {
+            type Reborrowed = #reborrowed;
+
+            fn reborrow(&#view self) -> Self::Reborrowed {
+                #body
+            }
+        }
+    })
+}
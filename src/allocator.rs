@@ -0,0 +1,89 @@
+//! Custom allocator support for `FlexWith`.
+//!
+//! This module provides [`FlexIn`], a [`FlexWith`] whose owner is a box
+//! parameterized by an [`Allocator`], along with the [`CloneIn`] trait used
+//! to claim borrowed data into a caller-chosen allocator.
+//!
+//! The allocator API is provided by the stable [`allocator_api2`] crate.
+//! Enabling the `nightly` feature switches it to the unstable
+//! `allocator_api` of the standard library, in which case [`FlexIn`] with
+//! the `Global` allocator is the very same type as [`Flex`](crate::Flex).
+
+use allocator_api2::alloc::Allocator;
+use allocator_api2::boxed::Box;
+use allocator_api2::vec::Vec;
+
+use super::FlexWith;
+
+/// A flexible container that can hold either a borrowed reference or a
+/// value boxed in the allocator `A`.
+///
+/// # Examples
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+/// use allocator_api2::alloc::Global;
+/// use flex::FlexIn;
+///
+/// let borrowed: FlexIn<[i32], Global> = FlexIn::Lend(&[1, 2, 3][..]);
+/// let owned: FlexIn<'static, [i32], Global> = borrowed.claim_in(Global);
+/// assert_eq!(&*owned, &[1, 2, 3]);
+/// ```
+pub type FlexIn<'a, T, A> = FlexWith<'a, T, Box<T, A>>;
+
+/// Types that can be cloned into a box in an arbitrary allocator.
+///
+/// This is implemented for all `Clone` types, for slices of `Clone` types
+/// and for `str`.
+pub trait CloneIn {
+    /// Clones `self` into a new box allocated in `alloc`.
+    fn clone_in<A: Allocator>(&self, alloc: A) -> Box<Self, A>;
+}
+
+impl<T: Clone> CloneIn for T {
+    fn clone_in<A: Allocator>(&self, alloc: A) -> Box<Self, A> {
+        Box::new_in(self.clone(), alloc)
+    }
+}
+
+impl<T: Clone> CloneIn for [T] {
+    fn clone_in<A: Allocator>(&self, alloc: A) -> Box<Self, A> {
+        let mut vec = Vec::with_capacity_in(self.len(), alloc);
+        vec.extend_from_slice(self);
+        vec.into_boxed_slice()
+    }
+}
+
+impl CloneIn for str {
+    fn clone_in<A: Allocator>(&self, alloc: A) -> Box<Self, A> {
+        let bytes = self.as_bytes().clone_in(alloc);
+        let (ptr, alloc) = Box::into_raw_with_allocator(bytes);
+
+        // SAFETY: The bytes were copied from a `str`, so they are valid
+        // UTF-8, and `str` has the same layout as `[u8]`.
+        unsafe { Box::from_raw_in(ptr as *mut str, alloc) }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "allocator-api")))]
+impl<'a, T: ?Sized + CloneIn, A: Allocator> FlexIn<'a, T, A> {
+    /// Converts into a `Box<T, A>`, consuming `self`.
+    ///
+    /// For `Lend` variants, this clones the borrowed data into a new box
+    /// allocated in `alloc`. For `Give` variants, this simply returns the
+    /// existing box and `alloc` is dropped.
+    pub fn into_box_in(self, alloc: A) -> Box<T, A> {
+        match self {
+            FlexWith::Lend(r) => r.clone_in(alloc),
+            FlexWith::Give(b) => b,
+        }
+    }
+
+    /// Claims ownership of the data, allocating in `alloc` if necessary.
+    ///
+    /// This is the allocator-aware equivalent of
+    /// [`claim`](FlexWith::claim).
+    pub fn claim_in<'b>(self, alloc: A) -> FlexIn<'b, T, A> {
+        FlexWith::Give(self.into_box_in(alloc))
+    }
+}
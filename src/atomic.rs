@@ -0,0 +1,243 @@
+//! A lock-free, hot-swappable `FlexArc` cell.
+//!
+//! [`AtomicFlex`] holds a [`FlexArc<'static, T>`](FlexArc), so it can point
+//! at either a `'static` value, such as a built-in default, or a shared
+//! owned value, such as freshly parsed configuration. Loads never block;
+//! stores wait for loads already in progress before reclaiming the value
+//! they replace.
+//!
+//! The value is kept in a heap node behind an atomic pointer. Each load
+//! registers itself in one of two reader counters, clones the current
+//! `FlexArc` out of the node and deregisters again. After swapping in a new
+//! node, a store points new loads at the other counter and waits until
+//! each counter has drained once, after which no load can still see the
+//! old node and it can be freed.
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::fmt::{Debug, Formatter, Result};
+use core::marker::PhantomData;
+
+#[cfg(loom)]
+use loom::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering::*},
+};
+
+#[cfg(not(loom))]
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering::*};
+
+use super::{FlexArc, FlexWith};
+
+#[cfg(loom)]
+fn spin() {
+    loom::thread::yield_now();
+}
+
+#[cfg(not(loom))]
+fn spin() {
+    core::hint::spin_loop();
+}
+
+/// A minimal stand-in for `loom::cell::UnsafeCell`, which lets loom check
+/// that no load reads a node after it has been reclaimed.
+#[cfg(not(loom))]
+struct UnsafeCell<T>(core::cell::UnsafeCell<T>);
+
+#[cfg(not(loom))]
+impl<T> UnsafeCell<T> {
+    fn new(value: T) -> Self {
+        Self(core::cell::UnsafeCell::new(value))
+    }
+
+    fn into_inner(self) -> T {
+        self.0.into_inner()
+    }
+
+    fn with<R>(&self, f: impl FnOnce(*const T) -> R) -> R {
+        f(self.0.get())
+    }
+
+    fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+        f(self.0.get())
+    }
+}
+
+type Node<T> = UnsafeCell<FlexArc<'static, T>>;
+
+/// Reclaims a node created by `AtomicFlex::node`.
+///
+/// # Safety
+///
+/// No load may be reading the node, now or later.
+unsafe fn reclaim<T: ?Sized>(node: *mut Node<T>) -> FlexArc<'static, T> {
+    let node = Box::from_raw(node);
+    node.with_mut(|_| ());
+    node.into_inner()
+}
+
+/// An atomic cell holding a [`FlexArc<'static, T>`](FlexArc).
+///
+/// `AtomicFlex` is meant for values that are read often and replaced
+/// rarely, like configuration that is hot-reloaded: it can start out
+/// pointing at a `'static` default table and later be swapped to an owned
+/// value without readers ever taking a lock.
+///
+/// - [`load`](AtomicFlex::load) is wait-free and returns a `FlexArc` that
+///   derefs to `T`. It keeps the value alive, so it stays valid even if the
+///   cell is updated while it is in use.
+/// - [`store`](AtomicFlex::store) and [`swap`](AtomicFlex::swap) replace the
+///   value. They wait for loads already in progress, but not for `FlexArc`s
+///   already returned, which own their own reference.
+///
+/// # Examples
+///
+/// ```
+/// use flex::{AtomicFlex, FlexArc};
+/// use std::sync::Arc;
+///
+/// static DEFAULT: [&str; 2] = ["localhost", "8080"];
+///
+/// let config = AtomicFlex::new(FlexArc::Lend(&DEFAULT));
+/// let before = config.load();
+/// assert!(matches!(before, FlexArc::Lend(_)));
+///
+/// config.store(FlexArc::Give(Arc::new(["example.com", "443"])));
+/// assert_eq!(config.load()[0], "example.com");
+///
+/// // Earlier loads still see the old value
+/// assert_eq!(before[0], "localhost");
+/// ```
+pub struct AtomicFlex<T: ?Sized + 'static> {
+    ptr: AtomicPtr<Node<T>>,
+    epoch: AtomicUsize,
+    readers: [AtomicUsize; 2],
+    writer: AtomicBool,
+    marker: PhantomData<FlexArc<'static, T>>,
+}
+
+// SAFETY: An `AtomicFlex` owns a `FlexArc<'static, T>` and hands out clones
+// of it to any thread, just like an `Arc<T>` would.
+unsafe impl<T: ?Sized + Send + Sync> Send for AtomicFlex<T> {}
+
+// SAFETY: See above.
+unsafe impl<T: ?Sized + Send + Sync> Sync for AtomicFlex<T> {}
+
+impl<T: ?Sized + 'static> AtomicFlex<T> {
+    /// Creates a new `AtomicFlex` holding `value`.
+    pub fn new(value: FlexArc<'static, T>) -> Self {
+        Self {
+            ptr: AtomicPtr::new(Self::node(value)),
+            epoch: AtomicUsize::new(0),
+            readers: [AtomicUsize::new(0), AtomicUsize::new(0)],
+            writer: AtomicBool::new(false),
+            marker: PhantomData,
+        }
+    }
+
+    /// Loads the current value.
+    ///
+    /// This never blocks: it takes a fixed number of atomic operations,
+    /// regardless of concurrent stores.
+    pub fn load(&self) -> FlexArc<'static, T> {
+        let readers = &self.readers[self.epoch.load(Acquire)];
+        readers.fetch_add(1, AcqRel);
+
+        // SAFETY: The node cannot be freed while we are registered in a
+        // reader counter: a store swaps the pointer before it waits for the
+        // counters to drain, so it either sees us registered or we see its
+        // new pointer.
+        let value = unsafe { (*self.ptr.load(Acquire)).with(|value| (*value).clone()) };
+
+        readers.fetch_sub(1, Release);
+        value
+    }
+
+    /// Stores `value`, dropping the previous value.
+    pub fn store(&self, value: FlexArc<'static, T>) {
+        drop(self.swap(value));
+    }
+
+    /// Stores `value`, returning the previous value.
+    pub fn swap(&self, value: FlexArc<'static, T>) -> FlexArc<'static, T> {
+        let new = Self::node(value);
+
+        while self
+            .writer
+            .compare_exchange_weak(false, true, Acquire, Relaxed)
+            .is_err()
+        {
+            spin();
+        }
+
+        let old = self.ptr.swap(new, AcqRel);
+
+        // Send new loads to the other counter while waiting for each one to
+        // drain, so that a steady stream of loads cannot starve the store.
+        let epoch = self.epoch.load(Relaxed);
+        for current in [epoch ^ 1, epoch] {
+            self.epoch.store(current, Release);
+
+            // A read-modify-write always sees the latest count, which a
+            // plain load of a counter modified elsewhere may not.
+            while self.readers[current ^ 1].fetch_add(0, AcqRel) != 0 {
+                spin();
+            }
+        }
+
+        self.writer.store(false, Release);
+
+        // SAFETY: No load can still be reading the old node.
+        unsafe { reclaim(old) }
+    }
+
+    /// Consumes the `AtomicFlex`, returning the current value.
+    pub fn into_inner(self) -> FlexArc<'static, T> {
+        let this = core::mem::ManuallyDrop::new(self);
+
+        // SAFETY: We own `this`, so there are no concurrent loads, and it
+        // will not be dropped, so the node is not freed twice.
+        unsafe { reclaim(this.ptr.load(Acquire)) }
+    }
+
+    fn node(value: FlexArc<'static, T>) -> *mut Node<T> {
+        Box::into_raw(Box::new(UnsafeCell::new(value)))
+    }
+}
+
+impl<T: ?Sized + 'static> Drop for AtomicFlex<T> {
+    fn drop(&mut self) {
+        // SAFETY: We are being dropped, so there are no concurrent loads.
+        drop(unsafe { reclaim(self.ptr.load(Acquire)) });
+    }
+}
+
+impl<T: ?Sized + Debug + 'static> Debug for AtomicFlex<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("AtomicFlex").field(&self.load()).finish()
+    }
+}
+
+impl<T: Default + 'static> Default for AtomicFlex<T> {
+    fn default() -> Self {
+        Self::new(FlexWith::Give(Arc::default()))
+    }
+}
+
+impl<T: ?Sized + 'static> From<FlexArc<'static, T>> for AtomicFlex<T> {
+    fn from(value: FlexArc<'static, T>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: ?Sized + 'static> From<&'static T> for AtomicFlex<T> {
+    fn from(value: &'static T) -> Self {
+        Self::new(FlexWith::Lend(value))
+    }
+}
+
+impl<T: ?Sized + 'static> From<Arc<T>> for AtomicFlex<T> {
+    fn from(value: Arc<T>) -> Self {
+        Self::new(FlexWith::Give(value))
+    }
+}
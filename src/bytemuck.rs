@@ -0,0 +1,243 @@
+//! Zero-copy casts between `Flex`es of plain-old-data types.
+//!
+//! Borrowed data is cast in place whenever its address is suitably aligned
+//! for the target type. Owned data keeps its allocation whenever the source
+//! and target types have the same alignment, since a box must be freed with
+//! the layout it was allocated with. Otherwise the data is copied into a new
+//! box, which needs the `alloc` feature.
+
+use core::fmt;
+use core::mem::size_of;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::mem::align_of;
+
+use bytemuck::{AnyBitPattern, NoUninit, PodCastError};
+
+use super::{Flex, FlexWith};
+
+/// The error returned when a `Flex<[A]>` cannot be cast to another type.
+///
+/// It gives back the original `Flex`, so no data is lost.
+///
+/// # Examples
+///
+/// ```
+/// use bytemuck::PodCastError;
+/// use flex::Flex;
+///
+/// let bytes = Flex::Lend(&[1u8, 2, 3][..]);
+/// let error = bytes.try_cast_slice::<u16>().unwrap_err();
+/// assert_eq!(error.pod_cast_error(), PodCastError::OutputSliceWouldHaveSlop);
+/// assert_eq!(&*error.into_flex(), &[1, 2, 3]);
+/// ```
+#[derive(Debug)]
+pub struct CastError<'a, A> {
+    flex: Flex<'a, [A]>,
+    error: PodCastError,
+}
+
+impl<'a, A> CastError<'a, A> {
+    /// Returns the slice that failed to cast.
+    pub fn as_slice(&self) -> &[A] {
+        &self.flex
+    }
+
+    /// Returns the original `Flex`.
+    pub fn into_flex(self) -> Flex<'a, [A]> {
+        self.flex
+    }
+
+    /// Returns the reason the cast failed.
+    pub fn pod_cast_error(&self) -> PodCastError {
+        self.error
+    }
+}
+
+impl<A> fmt::Display for CastError<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+// Returns the length of `len` elements of `A` as a slice of `B`, with the
+// same rules as `bytemuck::try_cast_slice`.
+fn cast_len<A, B>(len: usize) -> Result<usize, PodCastError> {
+    let bytes = len * size_of::<A>();
+
+    if size_of::<A>() == size_of::<B>() {
+        Ok(len)
+    } else if size_of::<B>() != 0 && bytes % size_of::<B>() == 0 {
+        Ok(bytes / size_of::<B>())
+    } else if size_of::<B>() == 0 && bytes == 0 {
+        Ok(0)
+    } else {
+        Err(PodCastError::OutputSliceWouldHaveSlop)
+    }
+}
+
+// Copies `data` into a new, suitably aligned slice of `len` elements.
+#[cfg(feature = "alloc")]
+fn copy<A: NoUninit, B: AnyBitPattern>(data: &[A], len: usize) -> Box<[B]> {
+    let bytes: &[u8] = bytemuck::cast_slice(data);
+    let size = size_of::<B>();
+
+    (0..len)
+        .map(|i| bytemuck::pod_read_unaligned(&bytes[i * size..][..size]))
+        .collect()
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+impl<'a, A: NoUninit> Flex<'a, [A]> {
+    /// Casts a slice to a slice of another plain-old-data type.
+    ///
+    /// A `Lend` stays borrowed if it is suitably aligned for `B`. A `Give`
+    /// keeps its allocation if `A` and `B` have the same alignment. In any
+    /// other case the data is copied into a new box, and without the `alloc`
+    /// feature a misaligned `Lend` is an error.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the size of the slice is not a multiple of the size of `B`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flex::Flex;
+    ///
+    /// let words = [0x0102u16, 0x0304];
+    /// let bytes = Flex::Lend(&words[..]).try_cast_slice::<u8>().unwrap();
+    /// assert!(matches!(bytes, Flex::Lend(_)));
+    /// assert_eq!(bytes.len(), 4);
+    /// ```
+    pub fn try_cast_slice<B: AnyBitPattern>(self) -> Result<Flex<'a, [B]>, CastError<'a, A>> {
+        #[cfg_attr(not(feature = "alloc"), allow(unused_variables))]
+        let len = match cast_len::<A, B>(self.len()) {
+            Ok(len) => len,
+            Err(error) => return Err(CastError { flex: self, error }),
+        };
+
+        match self {
+            FlexWith::Lend(r) => match bytemuck::try_cast_slice(r) {
+                Ok(slice) => Ok(FlexWith::Lend(slice)),
+
+                #[cfg(feature = "alloc")]
+                Err(..) => Ok(FlexWith::Give(copy(r, len))),
+
+                #[cfg(not(feature = "alloc"))]
+                Err(error) => Err(CastError {
+                    flex: FlexWith::Lend(r),
+                    error,
+                }),
+            },
+
+            // SAFETY: Both layouts have the same size, checked by `cast_len`,
+            // and the same alignment, so the box can be freed as a `[B]`.
+            // Every byte of an `A` is initialized and valid for a `B`.
+            #[cfg(feature = "alloc")]
+            FlexWith::Give(b) if align_of::<A>() == align_of::<B>() => {
+                let ptr = Box::into_raw(b) as *mut A as *mut B;
+                let slice = core::ptr::slice_from_raw_parts_mut(ptr, len);
+                Ok(FlexWith::Give(unsafe { Box::from_raw(slice) }))
+            }
+
+            #[cfg(feature = "alloc")]
+            FlexWith::Give(b) => Ok(FlexWith::Give(copy(&b, len))),
+
+            #[cfg(not(feature = "alloc"))]
+            FlexWith::Give(o) => o.never(),
+        }
+    }
+
+    /// Casts a slice to a slice of another plain-old-data type.
+    ///
+    /// This is [`try_cast_slice`](Self::try_cast_slice), but panics on
+    /// failure, like [`bytemuck::cast_slice`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice cannot be cast.
+    pub fn cast_slice<B: AnyBitPattern>(self) -> Flex<'a, [B]> {
+        match self.try_cast_slice() {
+            Ok(flex) => flex,
+            Err(error) => panic!("cast_slice: {}", error),
+        }
+    }
+
+    /// Casts a slice to a single value of a plain-old-data type.
+    ///
+    /// This is useful for reading a fixed-size header out of a buffer. As
+    /// with [`try_cast_slice`](Self::try_cast_slice), a suitably aligned
+    /// `Lend` stays borrowed, a `Give` keeps its allocation if `A` and `B`
+    /// have the same alignment, and the data is copied otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the size of the slice is not the size of `B`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flex::Flex;
+    ///
+    /// let words = [1u32, 2];
+    /// let pair = Flex::Lend(&words[..]).try_cast::<[u32; 2]>().unwrap();
+    /// assert_eq!(*pair, [1, 2]);
+    /// ```
+    pub fn try_cast<B: AnyBitPattern>(self) -> Result<Flex<'a, B>, CastError<'a, A>> {
+        if core::mem::size_of_val::<[A]>(&self) != size_of::<B>() {
+            return Err(CastError {
+                flex: self,
+                error: PodCastError::SizeMismatch,
+            });
+        }
+
+        match self {
+            FlexWith::Lend(r) => match bytemuck::try_from_bytes(bytemuck::cast_slice(r)) {
+                Ok(value) => Ok(FlexWith::Lend(value)),
+
+                #[cfg(feature = "alloc")]
+                Err(..) => Ok(FlexWith::Give(Box::new(bytemuck::pod_read_unaligned(
+                    bytemuck::cast_slice(r),
+                )))),
+
+                #[cfg(not(feature = "alloc"))]
+                Err(error) => Err(CastError {
+                    flex: FlexWith::Lend(r),
+                    error,
+                }),
+            },
+
+            // SAFETY: See `try_cast_slice`.
+            #[cfg(feature = "alloc")]
+            FlexWith::Give(b) if align_of::<A>() == align_of::<B>() => {
+                let ptr = Box::into_raw(b) as *mut A as *mut B;
+                Ok(FlexWith::Give(unsafe { Box::from_raw(ptr) }))
+            }
+
+            #[cfg(feature = "alloc")]
+            FlexWith::Give(b) => Ok(FlexWith::Give(Box::new(bytemuck::pod_read_unaligned(
+                bytemuck::cast_slice(&b),
+            )))),
+
+            #[cfg(not(feature = "alloc"))]
+            FlexWith::Give(o) => o.never(),
+        }
+    }
+
+    /// Casts a slice to a single value of a plain-old-data type.
+    ///
+    /// This is [`try_cast`](Self::try_cast), but panics on failure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice cannot be cast.
+    pub fn cast<B: AnyBitPattern>(self) -> Flex<'a, B> {
+        match self.try_cast() {
+            Ok(flex) => flex,
+            Err(error) => panic!("cast: {}", error),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Claim support for trait objects.
+//!
+//! [`claim`](crate::FlexWith::claim) and [`into_box`](crate::FlexWith::into_box)
+//! require `Box<T>: From<&'a T>`, which the standard library only provides
+//! for `Clone` types, `str` and slices. This module provides the object-safe
+//! [`CloneBox`] trait and the [`clone_box`] function, which together let a
+//! trait object be cloned into a new `Box` without knowing its concrete type,
+//! and [`claim_dyn`](crate::FlexWith::claim_dyn) and
+//! [`into_box_dyn`](crate::FlexWith::into_box_dyn), which claim any
+//! `CloneBox` type with them.
+
+use alloc::boxed::Box;
+
+use super::{Flex, FlexWith};
+
+mod private {
+    pub struct Private;
+}
+
+/// Types that can be cloned into a new `Box`, even behind a trait object.
+///
+/// `CloneBox` is implemented for all `Clone` types, for slices of `Clone`
+/// types and for `str`. It is object safe, so it can be used as a supertrait
+/// of your own traits, after which `Flex<'a, dyn Trait>` can be claimed with
+/// [`claim_dyn`](FlexWith::claim_dyn):
+///
+/// ```
+/// use flex::{CloneBox, Flex};
+///
+/// trait Shape: CloneBox {
+///     fn area(&self) -> f64;
+/// }
+///
+/// #[derive(Clone)]
+/// struct Square(f64);
+///
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// let square = Square(2.0);
+/// let borrowed: Flex<dyn Shape> = Flex::Lend(&square);
+/// let owned: Flex<'static, dyn Shape> = borrowed.claim_dyn();
+/// assert_eq!(owned.area(), 4.0);
+/// ```
+///
+/// Implementing `From<&dyn Trait>` and `Clone` for `Box<dyn Trait>` with
+/// [`clone_box`] also makes the plain [`claim`](FlexWith::claim) and `Clone`
+/// work for `Flex<'a, dyn Trait>`, as they do for any other type:
+///
+/// ```
+/// use flex::{clone_box, CloneBox, Flex};
+///
+/// trait Shape: CloneBox {
+///     fn area(&self) -> f64;
+/// }
+///
+/// impl<'a> From<&(dyn Shape + 'a)> for Box<dyn Shape + 'a> {
+///     fn from(shape: &(dyn Shape + 'a)) -> Self {
+///         clone_box(shape)
+///     }
+/// }
+///
+/// impl<'a> Clone for Box<dyn Shape + 'a> {
+///     fn clone(&self) -> Self {
+///         clone_box(&**self)
+///     }
+/// }
+///
+/// #[derive(Clone)]
+/// struct Square(f64);
+///
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// let square = Square(2.0);
+/// let borrowed: Flex<dyn Shape> = Flex::Lend(&square);
+/// let owned: Flex<'static, dyn Shape> = borrowed.claim();
+/// assert_eq!(owned.clone().area(), 4.0);
+/// ```
+pub trait CloneBox {
+    #[doc(hidden)]
+    fn __clone_box(&self, _: private::Private) -> *mut ();
+}
+
+impl<T: Clone> CloneBox for T {
+    fn __clone_box(&self, _: private::Private) -> *mut () {
+        Box::into_raw(Box::new(self.clone())) as *mut ()
+    }
+}
+
+impl<T: Clone> CloneBox for [T] {
+    fn __clone_box(&self, _: private::Private) -> *mut () {
+        Box::into_raw(Box::<[T]>::from(self)) as *mut ()
+    }
+}
+
+impl CloneBox for str {
+    fn __clone_box(&self, _: private::Private) -> *mut () {
+        Box::into_raw(Box::<str>::from(self)) as *mut ()
+    }
+}
+
+/// Clones a value into a new `Box`, even if it is a trait object.
+///
+/// # Examples
+///
+/// ```
+/// use flex::clone_box;
+///
+/// let slice: Box<[u8]> = clone_box(&[1, 2, 3][..]);
+/// assert_eq!(&*slice, &[1, 2, 3]);
+/// ```
+pub fn clone_box<T: ?Sized + CloneBox>(value: &T) -> Box<T> {
+    let ptr = value as *const T;
+    let data = value.__clone_box(private::Private);
+
+    // Offsetting the pointer moves its address to `data` and keeps its
+    // metadata, whatever the layout of a wide pointer.
+    let offset = (data as isize).wrapping_sub(ptr as *const () as isize);
+    let ptr = ptr.wrapping_byte_offset(offset);
+
+    // SAFETY: `__clone_box` returns a new allocation holding a clone of
+    // `value`, with the same type and metadata, which `ptr` now points to.
+    unsafe { Box::from_raw(ptr as *mut T) }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + CloneBox> Flex<'a, T> {
+    /// Converts the `Flex` into a `Box<T>` using [`clone_box`], consuming the
+    /// `Flex`.
+    ///
+    /// This is [`into_box`](FlexWith::into_box) for types that implement
+    /// [`CloneBox`] instead of `Box<T>: From<&T>`, such as trait objects with
+    /// `CloneBox` as a supertrait.
+    pub fn into_box_dyn(self) -> Box<T> {
+        match self {
+            FlexWith::Lend(r) => clone_box(r),
+            FlexWith::Give(b) => b,
+        }
+    }
+
+    /// Claims ownership of the data using [`clone_box`], converting borrowed
+    /// data to owned.
+    ///
+    /// This is [`claim`](FlexWith::claim) for types that implement
+    /// [`CloneBox`] instead of `Box<T>: From<&T>`.
+    pub fn claim_dyn<'b>(self) -> Flex<'b, T> {
+        FlexWith::Give(self.into_box_dyn())
+    }
+}
@@ -0,0 +1,259 @@
+//! A pointer-sized `Flex` for slices and strings.
+//!
+//! A `Flex<'a, str>` needs a separate tag to tell `Lend` from `Give`, which
+//! makes it larger than the `&str` it wraps. [`CompactFlex`] instead stores
+//! the tag in the highest bit of the length, which no valid allocation can
+//! use, so it is exactly as large as `&T`.
+
+use core::marker::PhantomData;
+use core::ptr::{self, NonNull};
+
+use alloc::boxed::Box;
+
+use super::{Flex, FlexWith};
+
+const GIVE: usize = 1 << (usize::BITS - 1);
+
+mod private {
+    pub trait Sealed {}
+
+    impl<T> Sealed for [T] {}
+    impl Sealed for str {}
+}
+
+/// Unsized types that a [`CompactFlex`] can hold: slices and `str`.
+///
+/// This trait is sealed and cannot be implemented outside this crate.
+pub trait Compact: private::Sealed {
+    #[doc(hidden)]
+    type Elem;
+
+    #[doc(hidden)]
+    fn as_parts(&self) -> (NonNull<Self::Elem>, usize);
+
+    #[doc(hidden)]
+    fn into_parts(boxed: Box<Self>) -> (NonNull<Self::Elem>, usize);
+
+    /// # Safety
+    ///
+    /// The parts must come from a valid `Self` that outlives `'a`.
+    #[doc(hidden)]
+    unsafe fn from_parts<'a>(ptr: NonNull<Self::Elem>, len: usize) -> &'a Self;
+
+    /// # Safety
+    ///
+    /// The parts must come from [`into_parts`](Compact::into_parts).
+    #[doc(hidden)]
+    unsafe fn box_from_parts(ptr: NonNull<Self::Elem>, len: usize) -> Box<Self>;
+}
+
+impl<T> Compact for [T] {
+    type Elem = T;
+
+    fn as_parts(&self) -> (NonNull<T>, usize) {
+        (NonNull::from(self).cast(), self.len())
+    }
+
+    fn into_parts(boxed: Box<Self>) -> (NonNull<T>, usize) {
+        let len = boxed.len();
+
+        // SAFETY: `Box::into_raw` never returns null.
+        (
+            unsafe { NonNull::new_unchecked(Box::into_raw(boxed) as *mut T) },
+            len,
+        )
+    }
+
+    unsafe fn from_parts<'a>(ptr: NonNull<T>, len: usize) -> &'a Self {
+        core::slice::from_raw_parts(ptr.as_ptr(), len)
+    }
+
+    unsafe fn box_from_parts(ptr: NonNull<T>, len: usize) -> Box<Self> {
+        Box::from_raw(ptr::slice_from_raw_parts_mut(ptr.as_ptr(), len))
+    }
+}
+
+impl Compact for str {
+    type Elem = u8;
+
+    fn as_parts(&self) -> (NonNull<u8>, usize) {
+        self.as_bytes().as_parts()
+    }
+
+    fn into_parts(boxed: Box<Self>) -> (NonNull<u8>, usize) {
+        <[u8]>::into_parts(boxed.into_boxed_bytes())
+    }
+
+    unsafe fn from_parts<'a>(ptr: NonNull<u8>, len: usize) -> &'a Self {
+        core::str::from_utf8_unchecked(<[u8]>::from_parts(ptr, len))
+    }
+
+    unsafe fn box_from_parts(ptr: NonNull<u8>, len: usize) -> Box<Self> {
+        alloc::str::from_boxed_utf8_unchecked(<[u8]>::box_from_parts(ptr, len))
+    }
+}
+
+/// A [`Flex`] for slices and strings that is the same size as `&T`.
+///
+/// `CompactFlex` stores whether it borrows or owns its data in the highest
+/// bit of the length, so `CompactFlex<'a, str>` takes 16 bytes on 64-bit
+/// targets where `Flex<'a, str>` takes 24. It derefs, compares, hashes and
+/// formats like a `Flex`, and converts to and from one without copying.
+///
+/// Since the length has one bit less to work with, borrowing or owning a
+/// slice of more than `isize::MAX` elements panics. This can only happen
+/// with zero-sized elements, as no allocation can be that large.
+///
+/// # Examples
+///
+/// ```
+/// use core::mem::size_of;
+/// use flex::{CompactFlex, Flex};
+///
+/// assert_eq!(size_of::<CompactFlex<str>>(), size_of::<&str>());
+///
+/// let borrowed = CompactFlex::lend("hello");
+/// assert!(borrowed.is_lend());
+///
+/// let owned: CompactFlex<'static, str> = borrowed.claim();
+/// assert!(owned.is_give());
+/// assert_eq!(owned, "hello");
+///
+/// let flex: Flex<str> = owned.into_flex();
+/// assert_eq!(flex, Flex::Lend("hello"));
+/// ```
+pub struct CompactFlex<'a, T: ?Sized + Compact> {
+    ptr: NonNull<T::Elem>,
+    len: usize,
+    marker: PhantomData<Flex<'a, T>>,
+}
+
+// SAFETY: A `CompactFlex` is either a `&'a T` or a `Box<T>`.
+unsafe impl<'a, T: ?Sized + Compact + Send + Sync> Send for CompactFlex<'a, T> {}
+
+// SAFETY: A `CompactFlex` is either a `&'a T` or a `Box<T>`.
+unsafe impl<'a, T: ?Sized + Compact + Sync> Sync for CompactFlex<'a, T> {}
+
+impl<'a, T: ?Sized + Compact> CompactFlex<'a, T> {
+    /// Creates a `CompactFlex` that borrows `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` has more than `isize::MAX` elements.
+    pub fn lend(value: &'a T) -> Self {
+        let (ptr, len) = value.as_parts();
+        if len & GIVE != 0 {
+            panic!("CompactFlex cannot borrow more than isize::MAX elements");
+        }
+
+        Self {
+            ptr,
+            len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates a `CompactFlex` that owns `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` has more than `isize::MAX` elements.
+    pub fn give(value: Box<T>) -> Self {
+        let (ptr, len) = T::into_parts(value);
+        if len & GIVE != 0 {
+            // SAFETY: The parts were just taken from the box.
+            drop(unsafe { T::box_from_parts(ptr, len) });
+            panic!("CompactFlex cannot own more than isize::MAX elements");
+        }
+
+        Self {
+            ptr,
+            len: len | GIVE,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the data is borrowed.
+    pub fn is_lend(&self) -> bool {
+        self.len & GIVE == 0
+    }
+
+    /// Returns `true` if the data is owned.
+    pub fn is_give(&self) -> bool {
+        !self.is_lend()
+    }
+
+    /// Converts into a [`Flex`] without copying or allocating.
+    pub fn into_flex(self) -> Flex<'a, T> {
+        let this = core::mem::ManuallyDrop::new(self);
+        let len = this.len & !GIVE;
+
+        // SAFETY: The parts describe a `&'a T` or a `Box<T>` according to
+        // the tag, and `this` will not be dropped.
+        unsafe {
+            if this.is_lend() {
+                FlexWith::Lend(T::from_parts(this.ptr, len))
+            } else {
+                FlexWith::Give(T::box_from_parts(this.ptr, len))
+            }
+        }
+    }
+
+    pub(crate) fn data(&self) -> &T {
+        // SAFETY: The parts describe a `&'a T` or a `Box<T>`, both of which
+        // are borrowed for as long as `self`.
+        unsafe { T::from_parts(self.ptr, self.len & !GIVE) }
+    }
+}
+
+impl<'a, T: ?Sized + Compact> CompactFlex<'a, T>
+where
+    Box<T>: From<&'a T>,
+{
+    /// Converts into a `Box<T>`, consuming the `CompactFlex`.
+    ///
+    /// Borrowed data is copied into a new box; owned data is returned as is.
+    pub fn into_box(self) -> Box<T> {
+        self.into_flex().into_box()
+    }
+
+    /// Claims ownership of the data, converting borrowed data to owned.
+    pub fn claim<'b>(self) -> CompactFlex<'b, T> {
+        CompactFlex::give(self.into_box())
+    }
+}
+
+impl<'a, T: ?Sized + Compact> Drop for CompactFlex<'a, T> {
+    fn drop(&mut self) {
+        if self.is_give() {
+            // SAFETY: The parts were taken from a box in `give`.
+            drop(unsafe { T::box_from_parts(self.ptr, self.len & !GIVE) });
+        }
+    }
+}
+
+impl<'a, T: ?Sized + Compact> Clone for CompactFlex<'a, T>
+where
+    Box<T>: for<'x> From<&'x T>,
+{
+    fn clone(&self) -> Self {
+        if self.is_lend() {
+            return Self {
+                ptr: self.ptr,
+                len: self.len,
+                marker: PhantomData,
+            };
+        }
+
+        Self::give(Box::from(self.data()))
+    }
+}
+
+impl<'a, T: ?Sized + Compact> Default for CompactFlex<'a, T>
+where
+    &'a T: Default,
+{
+    fn default() -> Self {
+        Self::lend(Default::default())
+    }
+}
@@ -1,39 +1,51 @@
-use core::borrow::Borrow;
+use core::borrow::{Borrow, BorrowMut};
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::Deref;
+use core::str::Utf8Error;
 
-use super::Flex;
+use super::{Flex, FlexMut, FlexWith};
 
 #[cfg(feature = "alloc")]
 use alloc::{
     borrow::{Cow, ToOwned},
     boxed::Box,
+    string::String,
+    vec::Vec,
 };
 
-impl<'a, T: ?Sized> Borrow<T> for Flex<'a, T> {
+#[cfg(feature = "alloc")]
+use alloc::ffi::CString;
+#[cfg(feature = "alloc")]
+use core::ffi::CStr;
+
+#[cfg(feature = "alloc")]
+use super::compact::{Compact, CompactFlex};
+
+impl<'a, T: ?Sized, O: Deref<Target = T>> Borrow<T> for FlexWith<'a, T, O> {
     fn borrow(&self) -> &T {
         self
     }
 }
 
-impl<'a, T: ?Sized + AsRef<U>, U: ?Sized> AsRef<U> for Flex<'a, T> {
+impl<'a, T: ?Sized + AsRef<U>, U: ?Sized, O: Deref<Target = T>> AsRef<U> for FlexWith<'a, T, O> {
     fn as_ref(&self) -> &U {
         match self {
-            Flex::Lend(r) => r.as_ref(),
-
-            #[cfg(feature = "alloc")]
-            Flex::Give(b) => (**b).as_ref(),
+            FlexWith::Lend(r) => r.as_ref(),
+            FlexWith::Give(o) => (**o).as_ref(),
         }
     }
 }
 
-impl<'a, T: ?Sized> From<&'a T> for Flex<'a, T> {
+impl<'a, T: ?Sized, O> From<&'a T> for FlexWith<'a, T, O> {
     fn from(r: &'a T) -> Self {
-        Flex::Lend(r)
+        FlexWith::Lend(r)
     }
 }
 
-impl<'a, T: ?Sized> From<&'a mut T> for Flex<'a, T> {
+impl<'a, T: ?Sized, O> From<&'a mut T> for FlexWith<'a, T, O> {
     fn from(r: &'a mut T) -> Self {
-        Flex::Lend(r)
+        FlexWith::Lend(r)
     }
 }
 
@@ -47,28 +59,374 @@ impl<T: ?Sized> From<Box<T>> for Flex<'_, T> {
 
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-impl<'a, T: ?Sized + ToOwned> From<Cow<'a, T>> for Flex<'a, T>
+impl<'a, T: ?Sized + ToOwned, O> From<Cow<'a, T>> for FlexWith<'a, T, O>
 where
-    T::Owned: Into<Box<T>>,
+    T::Owned: Into<O>,
 {
     fn from(c: Cow<'a, T>) -> Self {
         match c {
-            Cow::Borrowed(r) => Flex::Lend(r),
-            Cow::Owned(b) => Flex::Give(b.into()),
+            Cow::Borrowed(r) => FlexWith::Lend(r),
+            Cow::Owned(o) => FlexWith::Give(o.into()),
         }
     }
 }
 
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-impl<'a, T: ?Sized + ToOwned> From<Flex<'a, T>> for Cow<'a, T>
+impl<'a, T: ?Sized + ToOwned, O> From<FlexWith<'a, T, O>> for Cow<'a, T>
 where
-    T::Owned: From<Box<T>>,
+    T::Owned: From<O>,
 {
-    fn from(c: Flex<'a, T>) -> Self {
+    fn from(c: FlexWith<'a, T, O>) -> Self {
         match c {
-            Flex::Lend(r) => Cow::Borrowed(r),
-            Flex::Give(b) => Cow::Owned(b.into()),
+            FlexWith::Lend(r) => Cow::Borrowed(r),
+            FlexWith::Give(o) => Cow::Owned(o.into()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl From<String> for Flex<'_, str> {
+    fn from(s: String) -> Self {
+        Flex::Give(s.into_boxed_str())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl From<char> for Flex<'_, str> {
+    fn from(c: char) -> Self {
+        Flex::Give(String::from(c).into_boxed_str())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T> From<Vec<T>> for Flex<'_, [T]> {
+    fn from(v: Vec<T>) -> Self {
+        Flex::Give(v.into_boxed_slice())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T, const N: usize> From<[T; N]> for Flex<'_, [T]> {
+    fn from(array: [T; N]) -> Self {
+        Flex::Give(Box::new(array))
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl From<CString> for Flex<'_, CStr> {
+    fn from(s: CString) -> Self {
+        Flex::Give(s.into_boxed_c_str())
+    }
+}
+
+/// Converts a `Flex<str>` into a `String`, reusing the allocation of a
+/// `Give` and copying a `Lend`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl From<Flex<'_, str>> for String {
+    fn from(flex: Flex<'_, str>) -> Self {
+        match flex {
+            FlexWith::Lend(r) => r.into(),
+            FlexWith::Give(b) => b.into_string(),
+        }
+    }
+}
+
+/// Converts a `Flex<[T]>` into a `Vec<T>`, reusing the allocation of a
+/// `Give` and cloning a `Lend`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T: Clone> From<Flex<'_, [T]>> for Vec<T> {
+    fn from(flex: Flex<'_, [T]>) -> Self {
+        match flex {
+            FlexWith::Lend(r) => r.to_vec(),
+            FlexWith::Give(b) => b.into_vec(),
+        }
+    }
+}
+
+/// Converts a `Flex<CStr>` into a `CString`, reusing the allocation of a
+/// `Give` and copying a `Lend`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl From<Flex<'_, CStr>> for CString {
+    fn from(flex: Flex<'_, CStr>) -> Self {
+        match flex {
+            FlexWith::Lend(r) => r.into(),
+            FlexWith::Give(b) => b.into_c_string(),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Borrow<T> for FlexMut<'a, T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T: ?Sized> BorrowMut<T> for FlexMut<'a, T> {
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<'a, T: ?Sized + AsRef<U>, U: ?Sized> AsRef<U> for FlexMut<'a, T> {
+    fn as_ref(&self) -> &U {
+        match self {
+            FlexMut::Lend(r) => (**r).as_ref(),
+
+            #[cfg(feature = "alloc")]
+            FlexMut::Give(b) => (**b).as_ref(),
+        }
+    }
+}
+
+impl<'a, T: ?Sized + AsMut<U>, U: ?Sized> AsMut<U> for FlexMut<'a, T> {
+    fn as_mut(&mut self) -> &mut U {
+        match self {
+            FlexMut::Lend(r) => (**r).as_mut(),
+
+            #[cfg(feature = "alloc")]
+            FlexMut::Give(b) => (**b).as_mut(),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> From<&'a mut T> for FlexMut<'a, T> {
+    fn from(r: &'a mut T) -> Self {
+        FlexMut::Lend(r)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T: ?Sized> From<Box<T>> for FlexMut<'_, T> {
+    fn from(b: Box<T>) -> Self {
+        FlexMut::Give(b)
+    }
+}
+
+impl<'a, T: ?Sized> From<FlexMut<'a, T>> for Flex<'a, T> {
+    fn from(f: FlexMut<'a, T>) -> Self {
+        match f {
+            FlexMut::Lend(r) => Flex::Lend(r),
+
+            #[cfg(feature = "alloc")]
+            FlexMut::Give(b) => Flex::Give(b),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact> Borrow<T> for CompactFlex<'a, T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact + AsRef<U>, U: ?Sized> AsRef<U> for CompactFlex<'a, T> {
+    fn as_ref(&self) -> &U {
+        self.deref().as_ref()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact> From<&'a T> for CompactFlex<'a, T> {
+    fn from(value: &'a T) -> Self {
+        CompactFlex::lend(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T: ?Sized + Compact> From<Box<T>> for CompactFlex<'_, T> {
+    fn from(value: Box<T>) -> Self {
+        CompactFlex::give(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact> From<Flex<'a, T>> for CompactFlex<'a, T> {
+    fn from(flex: Flex<'a, T>) -> Self {
+        match flex {
+            FlexWith::Lend(r) => CompactFlex::lend(r),
+            FlexWith::Give(b) => CompactFlex::give(b),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact> From<CompactFlex<'a, T>> for Flex<'a, T> {
+    fn from(compact: CompactFlex<'a, T>) -> Self {
+        compact.into_flex()
+    }
+}
+
+/// The error returned when converting a `Flex<[u8]>` that is not valid
+/// UTF-8 into a `Flex<str>`.
+///
+/// Like `FromUtf8Error` for `String`, it gives back the original `Flex`,
+/// so no data is lost.
+///
+/// # Examples
+///
+/// ```
+/// use core::convert::TryFrom;
+/// use flex::Flex;
+///
+/// let bytes = Flex::Lend(&[b'h', 0xff][..]);
+/// let error = Flex::<str>::try_from(bytes).unwrap_err();
+/// assert_eq!(error.utf8_error().valid_up_to(), 1);
+/// assert!(matches!(error.into_flex(), Flex::Lend(b) if b == [b'h', 0xff]));
+/// ```
+#[derive(Debug)]
+pub struct FromUtf8Error<'a> {
+    flex: Flex<'a, [u8]>,
+    error: Utf8Error,
+}
+
+impl<'a> FromUtf8Error<'a> {
+    /// Returns the bytes that failed to convert.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.flex
+    }
+
+    /// Returns the original `Flex`.
+    pub fn into_flex(self) -> Flex<'a, [u8]> {
+        self.flex
+    }
+
+    /// Returns the details of the conversion failure.
+    pub fn utf8_error(&self) -> Utf8Error {
+        self.error
+    }
+}
+
+impl fmt::Display for FromUtf8Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+/// The error returned when converting a `Flex<[T]>` into a `Flex<[T; N]>`
+/// of a different length.
+///
+/// It gives back the original `Flex`, so no data is lost.
+///
+/// # Examples
+///
+/// ```
+/// use core::convert::TryFrom;
+/// use flex::Flex;
+///
+/// let slice = Flex::Lend(&[1, 2, 3][..]);
+/// let error = Flex::<[i32; 2]>::try_from(slice).unwrap_err();
+/// assert_eq!(error.to_string(), "expected 2 elements, found 3");
+/// assert_eq!(&*error.into_flex(), &[1, 2, 3]);
+/// ```
+#[derive(Debug)]
+pub struct FromSliceError<'a, T> {
+    flex: Flex<'a, [T]>,
+    expected: usize,
+}
+
+impl<'a, T> FromSliceError<'a, T> {
+    /// Returns the slice that failed to convert.
+    pub fn as_slice(&self) -> &[T] {
+        &self.flex
+    }
+
+    /// Returns the original `Flex`.
+    pub fn into_flex(self) -> Flex<'a, [T]> {
+        self.flex
+    }
+
+    /// Returns the length of the array that was expected.
+    pub fn expected(&self) -> usize {
+        self.expected
+    }
+}
+
+impl<T> fmt::Display for FromSliceError<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} elements, found {}",
+            self.expected,
+            self.flex.len()
+        )
+    }
+}
+
+impl<'a> From<Flex<'a, str>> for Flex<'a, [u8]> {
+    fn from(flex: Flex<'a, str>) -> Self {
+        match flex {
+            FlexWith::Lend(r) => FlexWith::Lend(r.as_bytes()),
+
+            #[cfg(feature = "alloc")]
+            FlexWith::Give(b) => FlexWith::Give(b.into_boxed_bytes()),
+
+            #[cfg(not(feature = "alloc"))]
+            FlexWith::Give(o) => o.never(),
+        }
+    }
+}
+
+impl<'a> TryFrom<Flex<'a, [u8]>> for Flex<'a, str> {
+    type Error = FromUtf8Error<'a>;
+
+    fn try_from(flex: Flex<'a, [u8]>) -> Result<Self, Self::Error> {
+        match core::str::from_utf8(&flex) {
+            Err(error) => Err(FromUtf8Error { flex, error }),
+            Ok(..) => Ok(match flex {
+                // SAFETY: The bytes were just validated as UTF-8.
+                FlexWith::Lend(r) => FlexWith::Lend(unsafe { core::str::from_utf8_unchecked(r) }),
+
+                // SAFETY: The bytes were just validated as UTF-8.
+                #[cfg(feature = "alloc")]
+                FlexWith::Give(b) => {
+                    FlexWith::Give(unsafe { alloc::str::from_boxed_utf8_unchecked(b) })
+                }
+
+                #[cfg(not(feature = "alloc"))]
+                FlexWith::Give(o) => o.never(),
+            }),
+        }
+    }
+}
+
+impl<'a, T, const N: usize> TryFrom<Flex<'a, [T]>> for Flex<'a, [T; N]> {
+    type Error = FromSliceError<'a, T>;
+
+    fn try_from(flex: Flex<'a, [T]>) -> Result<Self, Self::Error> {
+        let error = |flex| FromSliceError { flex, expected: N };
+
+        match flex {
+            FlexWith::Lend(r) => match <&[T; N]>::try_from(r) {
+                Ok(array) => Ok(FlexWith::Lend(array)),
+                Err(..) => Err(error(FlexWith::Lend(r))),
+            },
+
+            #[cfg(feature = "alloc")]
+            FlexWith::Give(b) => match Box::<[T; N]>::try_from(b) {
+                Ok(array) => Ok(FlexWith::Give(array)),
+                Err(b) => Err(error(FlexWith::Give(b))),
+            },
+
+            #[cfg(not(feature = "alloc"))]
+            FlexWith::Give(o) => o.never(),
         }
     }
 }
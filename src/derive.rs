@@ -1,68 +1,230 @@
-//! Derive implementations for `Flex`.
+//! Derive implementations for `FlexWith`.
 //!
 //! This module provides implementations of common traits such as `Clone`,
-//! `Eq`, `PartialEq`, `Ord`, `PartialOrd`, and `Hash` for the `Flex` type.
-//! In normal situations, these traits would be derived. But we need to proxy
-//! all the impls through `.derive()`.
+//! `Eq`, `PartialEq`, `Ord`, `PartialOrd`, and `Hash` for the `FlexWith`,
+//! `FlexMut` and `CompactFlex` types. In normal situations, these traits
+//! would be derived. But we need to proxy all the impls through `.derive()`.
 
 use core::hash::{Hash, Hasher};
-use core::ops::Deref;
+use core::ops::{Deref, DerefMut};
 
-use super::Flex;
+use super::{FlexMut, FlexWith};
 
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
 
-impl<'a, T: ?Sized> Deref for Flex<'a, T> {
+#[cfg(feature = "alloc")]
+use super::compact::{Compact, CompactFlex};
+
+impl<'a, T: ?Sized, O: Deref<Target = T>> Deref for FlexWith<'a, T, O> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
         match self {
-            Flex::Lend(r) => r,
-
-            #[cfg(feature = "alloc")]
-            Flex::Give(b) => b.deref(),
+            FlexWith::Lend(r) => r,
+            FlexWith::Give(o) => o.deref(),
         }
     }
 }
 
-#[cfg(not(feature = "alloc"))]
-impl<'a, T: ?Sized> Clone for Flex<'a, T> {
+impl<'a, T: ?Sized, O: Clone> Clone for FlexWith<'a, T, O> {
     fn clone(&self) -> Self {
         match self {
-            Flex::Lend(r) => Flex::Lend(*r),
+            FlexWith::Lend(r) => FlexWith::Lend(*r),
+            FlexWith::Give(o) => FlexWith::Give(o.clone()),
         }
     }
 }
 
+impl<'a, T: ?Sized + Eq, O: Deref<Target = T>> Eq for FlexWith<'a, T, O> {}
+
+impl<'a, T: ?Sized + PartialEq, O: Deref<Target = T>> PartialEq for FlexWith<'a, T, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref().eq(other.deref())
+    }
+}
+
+impl<'a, T: ?Sized + PartialEq, O: Deref<Target = T>> PartialEq<T> for FlexWith<'a, T, O> {
+    fn eq(&self, other: &T) -> bool {
+        self.deref() == other
+    }
+}
+
+impl<'a, T: ?Sized + PartialEq, O: Deref<Target = T>> PartialEq<&T> for FlexWith<'a, T, O> {
+    fn eq(&self, other: &&T) -> bool {
+        self.deref() == *other
+    }
+}
+
 #[cfg(feature = "alloc")]
-impl<'a, T: ?Sized> Clone for Flex<'a, T>
-where
-    Box<T>: Clone,
-{
-    fn clone(&self) -> Self {
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + PartialEq, O: Deref<Target = T>> PartialEq<Box<T>> for FlexWith<'a, T, O> {
+    fn eq(&self, other: &Box<T>) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+impl<'a, T: ?Sized + Ord, O: Deref<Target = T>> Ord for FlexWith<'a, T, O> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.deref().cmp(other.deref())
+    }
+}
+
+impl<'a, T: ?Sized + PartialOrd, O: Deref<Target = T>> PartialOrd for FlexWith<'a, T, O> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.deref().partial_cmp(other.deref())
+    }
+}
+
+impl<'a, T: ?Sized + PartialOrd, O: Deref<Target = T>> PartialOrd<T> for FlexWith<'a, T, O> {
+    fn partial_cmp(&self, other: &T) -> Option<core::cmp::Ordering> {
+        self.deref().partial_cmp(other)
+    }
+}
+
+impl<'a, T: ?Sized + PartialOrd, O: Deref<Target = T>> PartialOrd<&T> for FlexWith<'a, T, O> {
+    fn partial_cmp(&self, other: &&T) -> Option<core::cmp::Ordering> {
+        self.deref().partial_cmp(*other)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + PartialOrd, O: Deref<Target = T>> PartialOrd<Box<T>> for FlexWith<'a, T, O> {
+    fn partial_cmp(&self, other: &Box<T>) -> Option<core::cmp::Ordering> {
+        self.deref().partial_cmp(other.deref())
+    }
+}
+
+impl<'a, T: ?Sized + Hash, O: Deref<Target = T>> Hash for FlexWith<'a, T, O> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
+
+impl<'a, T: ?Sized> Deref for FlexMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
         match self {
-            Flex::Lend(r) => Flex::Lend(*r),
-            Flex::Give(b) => Flex::Give(b.clone()),
+            FlexMut::Lend(r) => r,
+
+            #[cfg(feature = "alloc")]
+            FlexMut::Give(b) => b.deref(),
         }
     }
 }
 
-impl<'a, T: ?Sized + Eq> Eq for Flex<'a, T> {}
+impl<'a, T: ?Sized> DerefMut for FlexMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            FlexMut::Lend(r) => r,
+
+            #[cfg(feature = "alloc")]
+            FlexMut::Give(b) => b.deref_mut(),
+        }
+    }
+}
+
+impl<'a, T: ?Sized + Eq> Eq for FlexMut<'a, T> {}
+
+impl<'a, T: ?Sized + PartialEq> PartialEq for FlexMut<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref().eq(other.deref())
+    }
+}
+
+impl<'a, T: ?Sized + PartialEq> PartialEq<T> for FlexMut<'a, T> {
+    fn eq(&self, other: &T) -> bool {
+        self.deref() == other
+    }
+}
+
+impl<'a, T: ?Sized + PartialEq> PartialEq<&T> for FlexMut<'a, T> {
+    fn eq(&self, other: &&T) -> bool {
+        self.deref() == *other
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + PartialEq> PartialEq<Box<T>> for FlexMut<'a, T> {
+    fn eq(&self, other: &Box<T>) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+impl<'a, T: ?Sized + Ord> Ord for FlexMut<'a, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.deref().cmp(other.deref())
+    }
+}
+
+impl<'a, T: ?Sized + PartialOrd> PartialOrd for FlexMut<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.deref().partial_cmp(other.deref())
+    }
+}
+
+impl<'a, T: ?Sized + PartialOrd> PartialOrd<T> for FlexMut<'a, T> {
+    fn partial_cmp(&self, other: &T) -> Option<core::cmp::Ordering> {
+        self.deref().partial_cmp(other)
+    }
+}
+
+impl<'a, T: ?Sized + PartialOrd> PartialOrd<&T> for FlexMut<'a, T> {
+    fn partial_cmp(&self, other: &&T) -> Option<core::cmp::Ordering> {
+        self.deref().partial_cmp(*other)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + PartialOrd> PartialOrd<Box<T>> for FlexMut<'a, T> {
+    fn partial_cmp(&self, other: &Box<T>) -> Option<core::cmp::Ordering> {
+        self.deref().partial_cmp(other.deref())
+    }
+}
+
+impl<'a, T: ?Sized + Hash> Hash for FlexMut<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact> Deref for CompactFlex<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.data()
+    }
+}
 
-impl<'a, T: ?Sized + PartialEq> PartialEq for Flex<'a, T> {
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact + Eq> Eq for CompactFlex<'a, T> {}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact + PartialEq> PartialEq for CompactFlex<'a, T> {
     fn eq(&self, other: &Self) -> bool {
         self.deref().eq(other.deref())
     }
 }
 
-impl<'a, T: ?Sized + PartialEq> PartialEq<T> for Flex<'a, T> {
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact + PartialEq> PartialEq<T> for CompactFlex<'a, T> {
     fn eq(&self, other: &T) -> bool {
         self.deref() == other
     }
 }
 
-impl<'a, T: ?Sized + PartialEq> PartialEq<&T> for Flex<'a, T> {
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact + PartialEq> PartialEq<&T> for CompactFlex<'a, T> {
     fn eq(&self, other: &&T) -> bool {
         self.deref() == *other
     }
@@ -70,31 +232,39 @@ impl<'a, T: ?Sized + PartialEq> PartialEq<&T> for Flex<'a, T> {
 
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-impl<'a, T: ?Sized + PartialEq> PartialEq<Box<T>> for Flex<'a, T> {
+impl<'a, T: ?Sized + Compact + PartialEq> PartialEq<Box<T>> for CompactFlex<'a, T> {
     fn eq(&self, other: &Box<T>) -> bool {
         self.deref() == other.deref()
     }
 }
 
-impl<'a, T: ?Sized + Ord> Ord for Flex<'a, T> {
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact + Ord> Ord for CompactFlex<'a, T> {
     fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.deref().cmp(other.deref())
     }
 }
 
-impl<'a, T: ?Sized + PartialOrd> PartialOrd for Flex<'a, T> {
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact + PartialOrd> PartialOrd for CompactFlex<'a, T> {
     fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         self.deref().partial_cmp(other.deref())
     }
 }
 
-impl<'a, T: ?Sized + PartialOrd> PartialOrd<T> for Flex<'a, T> {
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact + PartialOrd> PartialOrd<T> for CompactFlex<'a, T> {
     fn partial_cmp(&self, other: &T) -> Option<core::cmp::Ordering> {
         self.deref().partial_cmp(other)
     }
 }
 
-impl<'a, T: ?Sized + PartialOrd> PartialOrd<&T> for Flex<'a, T> {
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact + PartialOrd> PartialOrd<&T> for CompactFlex<'a, T> {
     fn partial_cmp(&self, other: &&T) -> Option<core::cmp::Ordering> {
         self.deref().partial_cmp(*other)
     }
@@ -102,13 +272,15 @@ impl<'a, T: ?Sized + PartialOrd> PartialOrd<&T> for Flex<'a, T> {
 
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-impl<'a, T: ?Sized + PartialOrd> PartialOrd<Box<T>> for Flex<'a, T> {
+impl<'a, T: ?Sized + Compact + PartialOrd> PartialOrd<Box<T>> for CompactFlex<'a, T> {
     fn partial_cmp(&self, other: &Box<T>) -> Option<core::cmp::Ordering> {
         self.deref().partial_cmp(other.deref())
     }
 }
 
-impl<'a, T: ?Sized + Hash> Hash for Flex<'a, T> {
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact + Hash> Hash for CompactFlex<'a, T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.deref().hash(state)
     }
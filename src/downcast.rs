@@ -0,0 +1,78 @@
+//! Downcasting for `FlexWith<dyn Any>` and `FlexWith<dyn Error>`.
+//!
+//! The consuming [`downcast`](FlexWith::downcast) preserves the variant: a
+//! `Lend` becomes a `Lend` of the concrete type and a `Give` becomes a
+//! `Give` of the concrete type, without copying or reallocating.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use core::any::Any;
+//! use flex::Flex;
+//!
+//! let value = 42u32;
+//! let flex: Flex<dyn Any> = Flex::Lend(&value);
+//! assert_eq!(flex.downcast_ref::<u32>(), Some(&42));
+//!
+//! let flex = flex.downcast::<u32>().unwrap();
+//! assert_eq!(flex, Flex::Lend(&42));
+//! # }
+//! ```
+
+use core::any::Any;
+use core::ops::{Deref, DerefMut};
+
+use core::error::Error;
+
+use super::FlexWith;
+
+#[cfg(feature = "alloc")]
+use super::Flex;
+
+macro_rules! downcast {
+    ($trait:ident $(+ $bound:ident)*) => {
+        impl<'a, O: Deref<Target = dyn $trait $(+ $bound)*>> FlexWith<'a, dyn $trait $(+ $bound)*, O> {
+            /// Returns a reference to the contents if they are of type `U`.
+            pub fn downcast_ref<U: $trait + 'static>(&self) -> Option<&U> {
+                (**self).downcast_ref()
+            }
+        }
+
+        impl<'a, O: DerefMut<Target = dyn $trait $(+ $bound)*>> FlexWith<'a, dyn $trait $(+ $bound)*, O> {
+            /// Returns a mutable reference to the owned contents if they are
+            /// of type `U`.
+            ///
+            /// Like [`get_mut`](FlexWith::get_mut), this returns `None` for
+            /// `Lend` variants.
+            pub fn downcast_mut<U: $trait + 'static>(&mut self) -> Option<&mut U> {
+                self.get_mut()?.downcast_mut()
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl<'a> Flex<'a, dyn $trait $(+ $bound)*> {
+            /// Attempts to downcast the contents to a concrete type,
+            /// consuming `self`.
+            ///
+            /// A `Lend` becomes `Lend(&U)` and a `Give` becomes
+            /// `Give(Box<U>)`. If the contents are not of type `U`, `self`
+            /// is returned unchanged.
+            pub fn downcast<U: $trait + 'static>(self) -> Result<Flex<'a, U>, Self> {
+                match self {
+                    FlexWith::Lend(r) => r.downcast_ref().map(FlexWith::Lend).ok_or(self),
+                    FlexWith::Give(b) => b.downcast().map(FlexWith::Give).map_err(FlexWith::Give),
+                }
+            }
+        }
+    };
+}
+
+downcast!(Any);
+downcast!(Any + Send);
+downcast!(Any + Send + Sync);
+
+downcast!(Error);
+downcast!(Error + Send);
+downcast!(Error + Send + Sync);
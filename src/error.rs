@@ -0,0 +1,85 @@
+//! Error implementations for `FlexWith`.
+//!
+//! This module forwards [`Error`] through `FlexWith`, so that a
+//! `Flex<dyn Error>` can be used as a `source()`, with `?` and with error
+//! handling crates. It also provides constructors and conversions between
+//! `Flex<dyn Error>` and `Box<dyn Error>`.
+//!
+//! Because `Flex<dyn Error>` is itself an `Error`, a blanket
+//! `From<E: Error>` implementation would conflict with the reflexive
+//! `From<T> for T`. Use [`from_error`](FlexWith::from_error) instead.
+
+use core::error::Error;
+use core::ops::Deref;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use super::{FlexWith, FromSliceError, FromUtf8Error};
+
+#[cfg(feature = "alloc")]
+use super::Flex;
+
+impl<'a, T: ?Sized + Error, O: Deref<Target = T>> Error for FlexWith<'a, T, O>
+where
+    O: core::fmt::Debug,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        (**self).source()
+    }
+
+    #[cfg(feature = "nightly")]
+    fn provide<'b>(&'b self, request: &mut core::error::Request<'b>) {
+        (**self).provide(request)
+    }
+}
+
+macro_rules! from_error {
+    ($(+ $bound:ident)*) => {
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl<'a> Flex<'a, dyn Error $(+ $bound)* + 'a> {
+            /// Creates an owned `Flex` by boxing an error.
+            pub fn from_error<E: Error $(+ $bound)* + 'a>(error: E) -> Self {
+                FlexWith::Give(Box::new(error))
+            }
+        }
+    };
+}
+
+from_error!();
+from_error!(+ Send);
+from_error!(+ Send + Sync);
+
+macro_rules! into_boxed_error {
+    ($(+ $bound:ident)*) => {
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl<'a> Flex<'a, dyn Error $(+ $bound)* + 'a> {
+            /// Converts into a boxed error, consuming `self`.
+            ///
+            /// For `Give` variants, this returns the existing box. For
+            /// `Lend` variants, this boxes the borrowed reference, so no
+            /// data is copied.
+            pub fn into_boxed_error(self) -> Box<dyn Error $(+ $bound)* + 'a> {
+                match self {
+                    FlexWith::Lend(r) => Box::new(r),
+                    FlexWith::Give(b) => b,
+                }
+            }
+        }
+    };
+}
+
+// `&(dyn Error + Send)` is not `Send`, so a borrowed `Send`-only error
+// cannot be boxed without losing the `Send` bound.
+into_boxed_error!();
+into_boxed_error!(+ Send + Sync);
+
+impl Error for FromUtf8Error<'_> {}
+
+impl<T: core::fmt::Debug> Error for FromSliceError<'_, T> {}
+
+#[cfg(feature = "bytemuck")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+impl<A: core::fmt::Debug> Error for super::CastError<'_, A> {}
@@ -0,0 +1,89 @@
+//! Fallible allocation support for `Flex`.
+//!
+//! This module provides the [`TryCloneBox`] trait, which clones a value into
+//! a `Box` while reporting allocation failure instead of aborting, along with
+//! the [`try_claim`](crate::FlexWith::try_claim) and
+//! [`try_into_box`](crate::FlexWith::try_into_box) methods built on it.
+
+use alloc::boxed::Box;
+use alloc::collections::TryReserveError;
+use alloc::vec::Vec;
+
+use super::{Flex, FlexWith};
+
+/// Types that can be cloned into a `Box` whose allocation may fail without
+/// aborting.
+///
+/// This is implemented for all `Clone` types, for slices of `Clone` types
+/// and for `str`. Only the allocation of the `Box` itself is fallible: the
+/// elements are cloned with `Clone`, so a type that allocates in its own
+/// `Clone` implementation, such as `String` or `Vec`, still aborts if that
+/// allocation fails.
+pub trait TryCloneBox {
+    /// Clones `self` into a new `Box`, returning an error if the allocation
+    /// fails.
+    fn try_clone_box(&self) -> Result<Box<Self>, TryReserveError>;
+}
+
+impl<T: Clone> TryCloneBox for T {
+    fn try_clone_box(&self) -> Result<Box<Self>, TryReserveError> {
+        let slice = core::slice::from_ref(self).try_clone_box()?;
+
+        // SAFETY: A boxed slice of length one has the same layout as a box
+        // of its only element.
+        Ok(unsafe { Box::from_raw(Box::into_raw(slice) as *mut T) })
+    }
+}
+
+impl<T: Clone> TryCloneBox for [T] {
+    fn try_clone_box(&self) -> Result<Box<Self>, TryReserveError> {
+        let mut vec = Vec::new();
+        vec.try_reserve_exact(self.len())?;
+        vec.extend_from_slice(self);
+        Ok(vec.into_boxed_slice())
+    }
+}
+
+impl TryCloneBox for str {
+    fn try_clone_box(&self) -> Result<Box<Self>, TryReserveError> {
+        let bytes = self.as_bytes().try_clone_box()?;
+
+        // SAFETY: The bytes were copied from a `str`, so they are valid UTF-8.
+        Ok(unsafe { alloc::str::from_boxed_utf8_unchecked(bytes) })
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + TryCloneBox> Flex<'a, T> {
+    /// Fallibly converts the `Flex` into a `Box<T>`, consuming the `Flex`.
+    ///
+    /// This is the fallible equivalent of [`into_box`](FlexWith::into_box):
+    /// for `Lend` variants, a failure to allocate the `Box` is returned as
+    /// an error instead of aborting. `Give` variants never fail.
+    pub fn try_into_box(self) -> Result<Box<T>, TryReserveError> {
+        match self {
+            FlexWith::Lend(r) => r.try_clone_box(),
+            FlexWith::Give(b) => Ok(b),
+        }
+    }
+
+    /// Fallibly claims ownership of the data, converting borrowed data to
+    /// owned.
+    ///
+    /// This is the fallible equivalent of [`claim`](FlexWith::claim). As
+    /// with [`TryCloneBox`], only the allocation of the `Box` is fallible;
+    /// cloning elements that allocate themselves can still abort.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flex::Flex;
+    ///
+    /// let borrowed = Flex::from("hello");
+    /// let owned: Flex<'static, str> = borrowed.try_claim().unwrap();
+    /// assert_eq!(&*owned, "hello");
+    /// ```
+    pub fn try_claim<'b>(self) -> Result<Flex<'b, T>, TryReserveError> {
+        self.try_into_box().map(FlexWith::Give)
+    }
+}
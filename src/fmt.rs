@@ -1,51 +1,127 @@
-use super::Flex;
+use super::{FlexMut, FlexWith};
+
+#[cfg(feature = "alloc")]
+use super::compact::{Compact, CompactFlex};
 
 use core::fmt::*;
 use core::ops::Deref;
 
-impl<'a, T: ?Sized + Display> Display for Flex<'a, T> {
+impl<'a, T: ?Sized + Display, O: Deref<Target = T>> Display for FlexWith<'a, T, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<'a, T: ?Sized + Binary, O: Deref<Target = T>> Binary for FlexWith<'a, T, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<'a, T: ?Sized + Octal, O: Deref<Target = T>> Octal for FlexWith<'a, T, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<'a, T: ?Sized + LowerHex, O: Deref<Target = T>> LowerHex for FlexWith<'a, T, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<'a, T: ?Sized + UpperHex, O: Deref<Target = T>> UpperHex for FlexWith<'a, T, O> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         self.deref().fmt(f)
     }
 }
 
-impl<'a, T: ?Sized + Binary> Binary for Flex<'a, T> {
+impl<'a, T: ?Sized + LowerExp, O: Deref<Target = T>> LowerExp for FlexWith<'a, T, O> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         self.deref().fmt(f)
     }
 }
 
-impl<'a, T: ?Sized + Octal> Octal for Flex<'a, T> {
+impl<'a, T: ?Sized + UpperExp, O: Deref<Target = T>> UpperExp for FlexWith<'a, T, O> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         self.deref().fmt(f)
     }
 }
 
-impl<'a, T: ?Sized + LowerHex> LowerHex for Flex<'a, T> {
+impl<'a, T: ?Sized, O: Deref<Target = T>> Pointer for FlexWith<'a, T, O> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         self.deref().fmt(f)
     }
 }
 
-impl<'a, T: ?Sized + UpperHex> UpperHex for Flex<'a, T> {
+impl<'a, T: ?Sized + Display> Display for FlexMut<'a, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         self.deref().fmt(f)
     }
 }
 
-impl<'a, T: ?Sized + LowerExp> LowerExp for Flex<'a, T> {
+impl<'a, T: ?Sized + Binary> Binary for FlexMut<'a, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         self.deref().fmt(f)
     }
 }
 
-impl<'a, T: ?Sized + UpperExp> UpperExp for Flex<'a, T> {
+impl<'a, T: ?Sized + Octal> Octal for FlexMut<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<'a, T: ?Sized + LowerHex> LowerHex for FlexMut<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<'a, T: ?Sized + UpperHex> UpperHex for FlexMut<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<'a, T: ?Sized + LowerExp> LowerExp for FlexMut<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<'a, T: ?Sized + UpperExp> UpperExp for FlexMut<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<'a, T: ?Sized> Pointer for FlexMut<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.deref().fmt(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact + Debug> Debug for CompactFlex<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let name = if self.is_lend() { "Lend" } else { "Give" };
+        f.debug_tuple(name).field(&self.deref()).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact + Display> Display for CompactFlex<'a, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         self.deref().fmt(f)
     }
 }
 
-impl<'a, T: ?Sized> Pointer for Flex<'a, T> {
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + Compact> Pointer for CompactFlex<'a, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         self.deref().fmt(f)
     }
@@ -0,0 +1,284 @@
+//! Inline, fixed-capacity owners for `FlexWith`.
+//!
+//! This module provides [`ArrayBuf`] and [`ArrayStr`], which own short
+//! slices and strings in a fixed-capacity buffer without allocating, and
+//! [`Own`], which owns a sized value by value. They make it possible to own
+//! data in a [`FlexWith`] even without the `alloc` feature, through the
+//! [`FlexBuf`], [`FlexStr`] and [`FlexOwn`] aliases.
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+use super::FlexWith;
+
+/// The error returned when a value exceeds the capacity of an inline owner.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CapacityError {
+    /// The capacity of the inline owner.
+    pub capacity: usize,
+
+    /// The length of the value that did not fit.
+    pub len: usize,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "length {} exceeds inline capacity {}",
+            self.len, self.capacity
+        )
+    }
+}
+
+/// A fixed-capacity buffer that owns up to `N` elements inline.
+///
+/// `ArrayBuf` dereferences to the `[T]` slice of its initialized elements.
+///
+/// # Examples
+///
+/// ```
+/// use core::convert::TryFrom;
+/// use flex::ArrayBuf;
+///
+/// let buf = ArrayBuf::<u8, 4>::try_from(&[1, 2][..]).unwrap();
+/// assert_eq!(&*buf, &[1, 2]);
+/// assert!(ArrayBuf::<u8, 4>::try_from(&[1, 2, 3, 4, 5][..]).is_err());
+/// ```
+#[derive(Copy, Clone)]
+pub struct ArrayBuf<T, const N: usize> {
+    buf: [T; N],
+    len: usize,
+}
+
+impl<T: Copy + Default, const N: usize> ArrayBuf<T, N> {
+    /// Creates a new, empty buffer.
+    pub fn new() -> Self {
+        Self {
+            buf: [T::default(); N],
+            len: 0,
+        }
+    }
+}
+
+impl<T, const N: usize> ArrayBuf<T, N> {
+    /// Returns the number of elements the buffer can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T: Copy + Default, const N: usize> Default for ArrayBuf<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for ArrayBuf<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.buf[..self.len]
+    }
+}
+
+impl<T, const N: usize> DerefMut for ArrayBuf<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buf[..self.len]
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayBuf<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<T: Copy + Default, const N: usize> TryFrom<&[T]> for ArrayBuf<T, N> {
+    type Error = CapacityError;
+
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        if slice.len() > N {
+            return Err(CapacityError {
+                capacity: N,
+                len: slice.len(),
+            });
+        }
+
+        let mut this = Self::new();
+        this.buf[..slice.len()].copy_from_slice(slice);
+        this.len = slice.len();
+        Ok(this)
+    }
+}
+
+/// A fixed-capacity string that owns up to `N` bytes inline.
+///
+/// `ArrayStr` dereferences to the `str` it holds.
+///
+/// # Examples
+///
+/// ```
+/// use core::convert::TryFrom;
+/// use flex::ArrayStr;
+///
+/// let s = ArrayStr::<8>::try_from("hello").unwrap();
+/// assert_eq!(&*s, "hello");
+/// assert!(ArrayStr::<4>::try_from("hello").is_err());
+/// ```
+#[derive(Copy, Clone, Default)]
+pub struct ArrayStr<const N: usize>(ArrayBuf<u8, N>);
+
+impl<const N: usize> ArrayStr<N> {
+    /// Creates a new, empty string.
+    pub fn new() -> Self {
+        Self(ArrayBuf::new())
+    }
+
+    /// Returns the number of bytes the string can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Deref for ArrayStr<N> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The bytes are only ever initialized from a `str`.
+        unsafe { core::str::from_utf8_unchecked(&self.0) }
+    }
+}
+
+impl<const N: usize> DerefMut for ArrayStr<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The bytes are only ever initialized from a `str`, and
+        // `&mut str` does not allow writing invalid UTF-8.
+        unsafe { core::str::from_utf8_unchecked_mut(&mut self.0) }
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArrayStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for ArrayStr<N> {
+    type Error = CapacityError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        ArrayBuf::try_from(s.as_bytes()).map(Self)
+    }
+}
+
+/// A flexible container that can hold either a borrowed slice or up to `N`
+/// elements inline.
+///
+/// # Examples
+///
+/// ```
+/// use flex::FlexBuf;
+///
+/// let borrowed: FlexBuf<u8, 16> = FlexBuf::Lend(&[1, 2, 3][..]);
+/// let owned: FlexBuf<'static, u8, 16> = borrowed.try_claim().unwrap();
+/// assert_eq!(&*owned, &[1, 2, 3]);
+/// ```
+pub type FlexBuf<'a, T, const N: usize> = FlexWith<'a, [T], ArrayBuf<T, N>>;
+
+/// A flexible container that can hold either a borrowed string or up to
+/// `N` bytes inline.
+///
+/// # Examples
+///
+/// ```
+/// use flex::FlexStr;
+///
+/// let borrowed: FlexStr<8> = FlexStr::Lend("hello");
+/// let owned: FlexStr<'static, 8> = borrowed.try_claim().unwrap();
+/// assert_eq!(&*owned, "hello");
+///
+/// let long: FlexStr<8> = FlexStr::Lend("hello, world");
+/// assert!(long.try_claim().is_err());
+/// ```
+pub type FlexStr<'a, const N: usize> = FlexWith<'a, str, ArrayStr<N>>;
+
+impl<'a, T: Copy + Default, const N: usize> FlexBuf<'a, T, N> {
+    /// Claims ownership of the data by copying it inline.
+    ///
+    /// For `Lend` variants, this returns an error if the borrowed slice is
+    /// longer than `N`. For `Give` variants, this never fails.
+    pub fn try_claim<'b>(self) -> Result<FlexBuf<'b, T, N>, CapacityError> {
+        match self {
+            FlexWith::Lend(r) => ArrayBuf::try_from(r).map(FlexWith::Give),
+            FlexWith::Give(o) => Ok(FlexWith::Give(o)),
+        }
+    }
+}
+
+impl<'a, const N: usize> FlexStr<'a, N> {
+    /// Claims ownership of the string by copying it inline.
+    ///
+    /// For `Lend` variants, this returns an error if the borrowed string is
+    /// longer than `N` bytes. For `Give` variants, this never fails.
+    pub fn try_claim<'b>(self) -> Result<FlexStr<'b, N>, CapacityError> {
+        match self {
+            FlexWith::Lend(r) => ArrayStr::try_from(r).map(FlexWith::Give),
+            FlexWith::Give(o) => Ok(FlexWith::Give(o)),
+        }
+    }
+}
+
+/// An owner that holds a sized value inline, by value.
+///
+/// Unlike `Box<T>`, owning a value with `Own` never allocates.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Own<T>(pub T);
+
+impl<T> Deref for Own<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Own<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Clone> From<&T> for Own<T> {
+    fn from(r: &T) -> Self {
+        Own(r.clone())
+    }
+}
+
+/// A flexible container that can hold either a borrowed reference or a
+/// sized value inline.
+///
+/// Since the owned value is stored by value, [`claim`](FlexWith::claim)
+/// clones into the `FlexOwn` itself rather than into a `Box<T>`.
+///
+/// # Examples
+///
+/// ```
+/// use flex::FlexOwn;
+///
+/// let value = 42u64;
+/// let borrowed = FlexOwn::Lend(&value);
+/// let owned: FlexOwn<'static, u64> = borrowed.claim();
+/// assert_eq!(*owned, 42);
+///
+/// let owned = FlexOwn::from(7u64);
+/// assert_eq!(owned, 7);
+/// ```
+pub type FlexOwn<'a, T> = FlexWith<'a, T, Own<T>>;
+
+impl<T> From<T> for FlexOwn<'_, T> {
+    fn from(value: T) -> Self {
+        FlexWith::Give(Own(value))
+    }
+}
@@ -0,0 +1,140 @@
+//! Iterator implementations for `Flex`.
+//!
+//! This module provides `FromIterator` and `Extend` for `Flex<[T]>` and
+//! `Flex<str>`, along with the by-value [`IntoIter`] iterator for
+//! `Flex<[T]>`.
+
+use core::iter::{Cloned, FromIterator, FusedIterator};
+use core::mem::replace;
+use core::slice::Iter;
+
+use alloc::string::String;
+use alloc::vec::{self, Vec};
+
+use super::Flex;
+
+impl<T> FromIterator<T> for Flex<'_, [T]> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Flex::Give(iter.into_iter().collect())
+    }
+}
+
+impl FromIterator<char> for Flex<'_, str> {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        Flex::Give(iter.into_iter().collect::<String>().into_boxed_str())
+    }
+}
+
+impl<'b> FromIterator<&'b str> for Flex<'_, str> {
+    fn from_iter<I: IntoIterator<Item = &'b str>>(iter: I) -> Self {
+        Flex::Give(iter.into_iter().collect::<String>().into_boxed_str())
+    }
+}
+
+impl<T: Clone> Extend<T> for Flex<'_, [T]> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut vec = match replace(self, Flex::Lend(&[])) {
+            Flex::Lend(r) => r.to_vec(),
+            Flex::Give(b) => b.into_vec(),
+        };
+
+        vec.extend(iter);
+        *self = Flex::Give(vec.into_boxed_slice());
+    }
+}
+
+impl Extend<char> for Flex<'_, str> {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        let mut string = into_string(replace(self, Flex::Lend("")));
+        string.extend(iter);
+        *self = Flex::Give(string.into_boxed_str());
+    }
+}
+
+impl<'b> Extend<&'b str> for Flex<'_, str> {
+    fn extend<I: IntoIterator<Item = &'b str>>(&mut self, iter: I) {
+        let mut string = into_string(replace(self, Flex::Lend("")));
+        string.extend(iter);
+        *self = Flex::Give(string.into_boxed_str());
+    }
+}
+
+fn into_string(flex: Flex<'_, str>) -> String {
+    match flex {
+        Flex::Lend(r) => String::from(r),
+        Flex::Give(b) => String::from(b),
+    }
+}
+
+/// A by-value iterator over the elements of a `Flex<[T]>`.
+///
+/// Elements are moved out of a `Give` box and cloned out of a `Lend`
+/// borrow. This is created by the `IntoIterator` implementation of
+/// `Flex<[T]>`.
+///
+/// # Examples
+///
+/// ```
+/// use flex::Flex;
+///
+/// let owned = Flex::Give(vec![String::from("a")].into_boxed_slice());
+/// let strings: Vec<String> = owned.into_iter().collect();
+/// assert_eq!(strings, ["a"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IntoIter<'a, T>(Inner<'a, T>);
+
+#[derive(Clone, Debug)]
+enum Inner<'a, T> {
+    Lend(Cloned<Iter<'a, T>>),
+    Give(vec::IntoIter<T>),
+}
+
+/// Iterates over the elements of a `Flex<[T]>` by value.
+///
+/// This takes precedence over the borrowing `IntoIterator` implementation
+/// of `&Flex<[T]>` in method calls: `flex.into_iter()` consumes `flex` and
+/// yields `T`, cloning the elements of a `Lend`. Use `flex.iter()` or
+/// `(&flex).into_iter()` to iterate by reference instead.
+impl<'a, T: Clone> IntoIterator for Flex<'a, [T]> {
+    type Item = T;
+    type IntoIter = IntoIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(match self {
+            Flex::Lend(r) => Inner::Lend(r.iter().cloned()),
+            Flex::Give(b) => Inner::Give(Vec::from(b).into_iter()),
+        })
+    }
+}
+
+impl<T: Clone> Iterator for IntoIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            Inner::Lend(i) => i.next(),
+            Inner::Give(i) => i.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            Inner::Lend(i) => i.size_hint(),
+            Inner::Give(i) => i.size_hint(),
+        }
+    }
+}
+
+impl<T: Clone> DoubleEndedIterator for IntoIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            Inner::Lend(i) => i.next_back(),
+            Inner::Give(i) => i.next_back(),
+        }
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for IntoIter<'_, T> {}
+
+impl<T: Clone> FusedIterator for IntoIter<'_, T> {}
@@ -1,19 +1,113 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(
+    feature = "nightly",
+    feature(allocator_api, error_generic_member_access)
+)]
 #![no_std]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod convert;
 mod derive;
+mod downcast;
+mod error;
 mod fmt;
+mod inline;
+mod map;
+mod owned;
+mod project;
+mod reborrow;
+mod unsize;
+
+#[cfg(feature = "alloc")]
+mod atomic;
+#[cfg(feature = "alloc")]
+mod claim;
+#[cfg(feature = "alloc")]
+mod compact;
+#[cfg(feature = "alloc")]
+mod fallible;
+#[cfg(feature = "alloc")]
+mod iter;
+#[cfg(feature = "alloc")]
+mod share;
 
-use core::ops::Index;
+#[cfg(feature = "allocator-api")]
+mod allocator;
+
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde;
+
+#[cfg(feature = "yoke")]
+mod yoke;
+
+#[cfg(feature = "zerofrom")]
+mod zerofrom;
+
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut, Index, IndexMut};
 
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
 
+pub use convert::{FromSliceError, FromUtf8Error};
+pub use inline::{ArrayBuf, ArrayStr, CapacityError, FlexBuf, FlexOwn, FlexStr, Own};
+pub use owned::IntoOwned;
+pub use project::{FlexProjected, Projectable};
+pub use reborrow::Reborrow;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use atomic::AtomicFlex;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use claim::{clone_box, CloneBox};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use compact::{Compact, CompactFlex};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use fallible::TryCloneBox;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use iter::IntoIter;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use share::{FlexArc, FlexRc};
+
+#[cfg(feature = "allocator-api")]
+#[cfg_attr(docsrs, doc(cfg(feature = "allocator-api")))]
+pub use allocator::{CloneIn, FlexIn};
+
+#[cfg(feature = "bytemuck")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+pub use self::bytemuck::CastError;
+
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use flex_macros::{claimable, IntoOwned, Reborrow};
+
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::boxed::Box;
+}
+
 /// A flexible container that can hold either a borrowed reference or an
 /// owned boxed value.
 ///
@@ -22,15 +116,21 @@ use alloc::boxed::Box;
 /// particularly useful for unsized types where the owned representation
 /// is naturally `Box<T>` rather than a separate container type.
 ///
+/// `Flex` is a [`FlexWith`] whose owner is `Box<T>`. See [`FlexWith`] for
+/// using other owners such as `Rc<T>` or `Arc<T>`.
+///
 /// # Variants
 ///
-/// - [`Lend`](Flex::Lend): Holds a borrowed reference `&'a T`
-/// - [`Give`](Flex::Give): Holds an owned `Box<T>` (requires `alloc`
+/// - [`Lend`](FlexWith::Lend): Holds a borrowed reference `&'a T`
+/// - [`Give`](FlexWith::Give): Holds an owned `Box<T>` (requires `alloc`
 ///   feature)
 ///
+/// Without the `alloc` feature, the owner is [`Unowned`], so a `Flex` can
+/// only ever be `Lend`.
+///
 /// # Comparison with Other Types
 ///
-/// Unlike [`Cow`](std::borrow::Cow), which works with type pairs like
+/// Unlike [`Cow`](alloc::borrow::Cow), which works with type pairs like
 /// `str`/`String` or `[T]`/`Vec<T>`, `Flex` works with a single type in
 /// two ownership models. This makes it ideal for trait objects and other
 /// unsized types where there isn't a natural "owned" container type.
@@ -63,29 +163,144 @@ use alloc::boxed::Box;
 /// let borrowed = Flex::from(s.as_str());
 /// assert_eq!(&*borrowed, "hello");
 /// ```
+#[cfg(feature = "alloc")]
+pub type Flex<'a, T> = FlexWith<'a, T, Box<T>>;
+
+/// A flexible container that can hold a borrowed reference.
+///
+/// Without the `alloc` feature there is no `Box<T>` to own data with, so
+/// the owner is [`Unowned`] and a `Flex` can only ever be
+/// [`Lend`](FlexWith::Lend). Enable the `alloc` feature to own data in a
+/// `Box<T>`.
+///
+/// # Examples
+///
+/// ```
+/// use flex::Flex;
+///
+/// let borrowed = Flex::from("hello");
+/// assert_eq!(&*borrowed, "hello");
+/// ```
+#[cfg(not(feature = "alloc"))]
+pub type Flex<'a, T> = FlexWith<'a, T, Unowned<T>>;
+
+/// A flexible container that can hold either a borrowed reference or a
+/// value owned by `O`.
+///
+/// `FlexWith` is the generic form of [`Flex`]: the owner `O` can be any
+/// type that dereferences to `T`, such as `Box<T>`, `Rc<T>`, `Arc<T>`, a
+/// memory-mapped buffer or a custom handle type. All of the trait
+/// implementations of [`Flex`] are available for any owner that implements
+/// `Deref<Target = T>`, and [`claim`](FlexWith::claim) is available when
+/// the owner can also be created from `&T`.
+///
+#[cfg_attr(
+    feature = "alloc",
+    doc = "Prefer the [`Flex`], [`FlexRc`] and [`FlexArc`] aliases where possible,",
+    doc = "since they fix the owner type and so never need extra annotations."
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "Prefer the [`Flex`] alias where possible, since it fixes the owner",
+    doc = "type and so never needs extra annotations."
+)]
+///
+/// # Examples
+///
+/// ```
+/// use flex::FlexWith;
+/// use std::ops::Deref;
+///
+/// struct Handle(&'static str);
+///
+/// impl Deref for Handle {
+///     type Target = str;
+///
+///     fn deref(&self) -> &str {
+///         self.0
+///     }
+/// }
+///
+/// let flex: FlexWith<str, Handle> = FlexWith::Give(Handle("hello"));
+/// assert_eq!(&*flex, "hello");
+/// ```
 #[derive(Debug)]
-pub enum Flex<'a, T: ?Sized> {
+pub enum FlexWith<'a, T: ?Sized, O> {
     /// A borrowed reference to data with lifetime `'a`.
     Lend(&'a T),
 
-    /// An owned, heap-allocated value.
+    /// An owned value, held by the owner `O`.
+    Give(O),
+}
+
+/// An owner that can never be constructed.
+///
+/// This is the owner of [`Flex`] when the `alloc` feature is disabled.
+/// Since no `Unowned` value can exist, a [`FlexWith::Give`] holding one can
+/// never exist either. Its fields are public so that other crates can see
+/// this too, and a `Flex` matched by value only needs a `Lend` arm. When
+/// matching by reference, use [`never`](Unowned::never) to discharge the
+/// `Give` arm.
+pub struct Unowned<T: ?Sized>(pub Infallible, pub PhantomData<T>);
+
+impl<T: ?Sized> Unowned<T> {
+    /// Proves that this code is unreachable.
     ///
-    /// Only available with the `alloc` feature.
-    #[cfg(feature = "alloc")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-    Give(Box<T>),
+    /// # Examples
+    ///
+    /// ```
+    /// use flex::{FlexWith, Unowned};
+    ///
+    /// fn len(flex: &FlexWith<str, Unowned<str>>) -> usize {
+    ///     match flex {
+    ///         FlexWith::Lend(r) => r.len(),
+    ///         FlexWith::Give(o) => o.never(),
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(len(&FlexWith::Lend("hello")), 5);
+    /// ```
+    pub fn never(&self) -> ! {
+        match self.0 {}
+    }
+}
+
+impl<T: ?Sized> Deref for Unowned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.never()
+    }
+}
+
+impl<T: ?Sized> DerefMut for Unowned<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.never()
+    }
+}
+
+impl<T: ?Sized> Clone for Unowned<T> {
+    fn clone(&self) -> Self {
+        self.never()
+    }
+}
+
+impl<T: ?Sized> core::fmt::Debug for Unowned<T> {
+    fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.never()
+    }
 }
 
-impl<'a, T: ?Sized> Default for Flex<'a, T>
+impl<'a, T: ?Sized, O> Default for FlexWith<'a, T, O>
 where
     &'a T: Default,
 {
     fn default() -> Self {
-        Flex::Lend(Default::default())
+        FlexWith::Lend(Default::default())
     }
 }
 
-impl<'a, T: ?Sized + Index<I>, I> Index<I> for Flex<'a, T> {
+impl<'a, T: ?Sized + Index<I>, O: Deref<Target = T>, I> Index<I> for FlexWith<'a, T, O> {
     type Output = T::Output;
 
     fn index(&self, index: I) -> &Self::Output {
@@ -93,7 +308,7 @@ impl<'a, T: ?Sized + Index<I>, I> Index<I> for Flex<'a, T> {
     }
 }
 
-impl<'a, T: ?Sized> IntoIterator for &'a Flex<'a, T>
+impl<'a, T: ?Sized, O: Deref<Target = T>> IntoIterator for &'a FlexWith<'a, T, O>
 where
     &'a T: IntoIterator,
 {
@@ -105,28 +320,76 @@ where
     }
 }
 
-#[cfg(feature = "alloc")]
-#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-impl<'a, T: ?Sized> Flex<'a, T>
-where
-    Box<T>: From<&'a T>,
-{
-    /// Converts the `Flex` into a `Box<T>`, consuming the `Flex`.
+impl<'a, T: ?Sized, O: Deref<Target = T>> FlexWith<'a, T, O> {
+    /// Borrows the contents as a new `Lend`, without copying.
     ///
-    /// For `Lend` variants, this allocates a new `Box<T>` from the borrowed reference.
-    /// For `Give` variants, this simply returns the owned `Box<T>`.
-    pub fn into_box(self) -> Box<T> {
+    /// This is the reverse of [`claim`](FlexWith::claim): it turns a
+    /// `&'b FlexWith<'a, T, O>` of either variant into a cheap
+    /// `FlexWith<'b, T, O>` that points into the original.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flex::Flex;
+    ///
+    /// # #[cfg(feature = "alloc")] {
+    /// let owned: Flex<'static, str> = Flex::Give("hello".into());
+    /// let view: Flex<'_, str> = owned.reborrow();
+    /// assert!(matches!(view, Flex::Lend("hello")));
+    /// # }
+    /// ```
+    pub fn reborrow<'b>(&'b self) -> FlexWith<'b, T, O> {
+        FlexWith::Lend(self)
+    }
+}
+
+impl<'a, T: ?Sized, O: DerefMut<Target = T>> FlexWith<'a, T, O> {
+    /// Returns a mutable reference to the owned data, if any.
+    ///
+    /// For `Lend` variants, this returns `None` since borrowed data cannot
+    /// be mutated. For `Give` variants, this returns the contents of the
+    /// owner. This method never allocates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flex::Flex;
+    ///
+    /// let mut borrowed = Flex::Lend(&[1, 2, 3][..]);
+    /// assert!(borrowed.get_mut().is_none());
+    ///
+    /// # #[cfg(feature = "alloc")] {
+    /// let mut owned = Flex::Give(vec![1, 2, 3].into_boxed_slice());
+    /// owned.get_mut().unwrap()[0] = 4;
+    /// assert_eq!(&*owned, &[4, 2, 3]);
+    /// # }
+    /// ```
+    pub fn get_mut(&mut self) -> Option<&mut T> {
         match self {
-            Flex::Lend(r) => Box::from(r),
-            Flex::Give(b) => b,
+            FlexWith::Lend(..) => None,
+            FlexWith::Give(o) => Some(o),
+        }
+    }
+}
+
+impl<'a, T: ?Sized, O: Deref<Target = T> + From<&'a T>> FlexWith<'a, T, O> {
+    /// Converts the `FlexWith` into its owner, consuming the `FlexWith`.
+    ///
+    /// For `Lend` variants, this creates a new owner from the borrowed reference.
+    /// For `Give` variants, this simply returns the existing owner.
+    pub fn into_owner(self) -> O {
+        match self {
+            FlexWith::Lend(r) => O::from(r),
+            FlexWith::Give(o) => o,
         }
     }
 
     /// Claims ownership of the data, converting borrowed data to owned.
     ///
     /// For `Lend` variants, this converts the borrowed data into an owned
-    /// `Box<T>` by using `Box<T>::from(&T)`. This typically involves
-    /// cloning or allocating the data.
+    /// value by using `O::from(&T)`. For a [`Flex`] this is
+    /// `Box<T>::from(&T)`, which typically involves cloning or allocating
+    /// the data.
     ///
     /// For `Give` variants, this is a no-op that simply changes the
     /// lifetime bound, as the data is already owned.
@@ -136,20 +399,217 @@ where
     /// ```
     /// use flex::Flex;
     ///
+    /// # #[cfg(feature = "alloc")] {
     /// let borrowed = Flex::from(&[1, 2, 3][..]);
     /// let owned: Flex<'static, [i32]> = borrowed.claim();
     /// assert_eq!(&*owned, &[1, 2, 3]);
+    /// # }
     /// ```
     ///
     /// ```
     /// use flex::Flex;
     ///
+    /// # #[cfg(feature = "alloc")] {
     /// let s = "hello";
     /// let borrowed = Flex::from(s);
     /// let owned: Flex<'static, str> = borrowed.claim();
     /// assert_eq!(&*owned, "hello");
+    /// # }
+    /// ```
+    pub fn claim<'b>(self) -> FlexWith<'b, T, O> {
+        FlexWith::Give(self.into_owner())
+    }
+
+    /// Acquires a mutable reference to the data, claiming it if necessary.
+    ///
+    /// For `Lend` variants, this first converts the borrowed data into an
+    /// owned value (see [`claim`](FlexWith::claim)), replacing `self` with a
+    /// `Give` variant. For `Give` variants, this simply returns the contents
+    /// of the owner.
+    ///
+    /// This is the `Flex` equivalent of `Cow::to_mut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flex::Flex;
+    ///
+    /// # #[cfg(feature = "alloc")] {
+    /// let mut flex = Flex::Lend(&[1, 2, 3][..]);
+    /// flex.to_mut()[0] = 4;
+    /// assert!(matches!(flex, Flex::Give(..)));
+    /// assert_eq!(&*flex, &[4, 2, 3]);
+    /// # }
+    /// ```
+    pub fn to_mut(&mut self) -> &mut T
+    where
+        O: DerefMut,
+    {
+        if let FlexWith::Lend(r) = *self {
+            *self = FlexWith::Give(O::from(r));
+        }
+
+        match self {
+            FlexWith::Lend(..) => unreachable!(),
+            FlexWith::Give(o) => o,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized> Flex<'a, T>
+where
+    Box<T>: From<&'a T>,
+{
+    /// Converts the `Flex` into a `Box<T>`, consuming the `Flex`.
+    ///
+    /// For `Lend` variants, this allocates a new `Box<T>` from the borrowed reference.
+    /// For `Give` variants, this simply returns the owned `Box<T>`.
+    pub fn into_box(self) -> Box<T> {
+        self.into_owner()
+    }
+}
+
+/// A flexible container that can hold either a mutable borrowed reference
+/// or an owned boxed value.
+///
+/// `FlexMut` is the mutable counterpart of [`Flex`]. Where [`Flex::Lend`]
+/// holds a shared reference `&'a T`, [`FlexMut::Lend`] holds an exclusive
+/// reference `&'a mut T`. Both variants therefore allow mutation through
+/// [`DerefMut`], which makes it possible to call `&mut self` methods on
+/// trait objects regardless of who owns them.
+///
+/// A `FlexMut` can always be converted into a [`Flex`] without allocating.
+///
+/// # Variants
+///
+/// - [`Lend`](FlexMut::Lend): Holds a mutable borrowed reference `&'a mut T`
+/// - `Give`: Holds an owned `Box<T>` (requires `alloc`
+///   feature)
+///
+/// # Examples
+///
+/// ```
+/// use flex::FlexMut;
+///
+/// trait Counter {
+///     fn bump(&mut self);
+/// }
+///
+/// impl Counter for u32 {
+///     fn bump(&mut self) {
+///         *self += 1;
+///     }
+/// }
+///
+/// fn bump_twice(mut counter: FlexMut<dyn Counter>) {
+///     counter.bump();
+///     counter.bump();
+/// }
+///
+/// let mut value = 40u32;
+/// bump_twice(FlexMut::from(&mut value as &mut dyn Counter));
+/// assert_eq!(value, 42);
+///
+/// # #[cfg(feature = "alloc")] {
+/// bump_twice(FlexMut::from(Box::new(0u32) as Box<dyn Counter>));
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum FlexMut<'a, T: ?Sized> {
+    /// A mutable borrowed reference to data with lifetime `'a`.
+    Lend(&'a mut T),
+
+    /// An owned, heap-allocated value.
+    ///
+    /// Only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    Give(Box<T>),
+}
+
+impl<'a, T: ?Sized> Default for FlexMut<'a, T>
+where
+    &'a mut T: Default,
+{
+    fn default() -> Self {
+        FlexMut::Lend(Default::default())
+    }
+}
+
+impl<'a, T: ?Sized + Index<I>, I> Index<I> for FlexMut<'a, T> {
+    type Output = T::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        Index::index(&**self, index)
+    }
+}
+
+impl<'a, T: ?Sized + IndexMut<I>, I> IndexMut<I> for FlexMut<'a, T> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        IndexMut::index_mut(&mut **self, index)
+    }
+}
+
+impl<'a, 'b, T: ?Sized> IntoIterator for &'b FlexMut<'a, T>
+where
+    &'b T: IntoIterator,
+{
+    type Item = <&'b T as IntoIterator>::Item;
+    type IntoIter = <&'b T as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (**self).into_iter()
+    }
+}
+
+impl<'a, 'b, T: ?Sized> IntoIterator for &'b mut FlexMut<'a, T>
+where
+    &'b mut T: IntoIterator,
+{
+    type Item = <&'b mut T as IntoIterator>::Item;
+    type IntoIter = <&'b mut T as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (**self).into_iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized> FlexMut<'a, T>
+where
+    Box<T>: From<&'a T>,
+{
+    /// Converts the `FlexMut` into a `Box<T>`, consuming the `FlexMut`.
+    ///
+    /// For `Lend` variants, this allocates a new `Box<T>` from the borrowed reference.
+    /// For `Give` variants, this simply returns the owned `Box<T>`.
+    pub fn into_box(self) -> Box<T> {
+        match self {
+            FlexMut::Lend(r) => Box::from(&*r),
+            FlexMut::Give(b) => b,
+        }
+    }
+
+    /// Claims ownership of the data, converting borrowed data to owned.
+    ///
+    /// This releases the mutable borrow, leaving the original data
+    /// untouched by any further mutation through the returned value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flex::FlexMut;
+    ///
+    /// let mut data = [1, 2, 3];
+    /// let mut owned: FlexMut<'static, [i32]> = FlexMut::from(&mut data[..]).claim();
+    /// owned[0] = 4;
+    /// assert_eq!(&*owned, &[4, 2, 3]);
+    /// assert_eq!(data, [1, 2, 3]);
     /// ```
-    pub fn claim<'b>(self) -> Flex<'b, T> {
-        Flex::Give(self.into_box())
+    pub fn claim<'b>(self) -> FlexMut<'b, T> {
+        FlexMut::Give(self.into_box())
     }
 }
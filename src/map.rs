@@ -0,0 +1,122 @@
+//! Variant-preserving projections for `FlexWith`.
+//!
+//! [`map`](FlexWith::map) and [`try_map`](FlexWith::try_map) convert a
+//! `FlexWith<'a, T, O>` into a `FlexWith<'a, U, P>` with one closure per
+//! variant, so a `Lend` stays a `Lend` and a `Give` stays a `Give`.
+//! [`map_or_claim`](FlexWith::map_or_claim) takes a single projection and
+//! claims the projected data when the owner itself cannot be projected.
+
+use core::ops::Deref;
+
+use super::FlexWith;
+
+impl<'a, T: ?Sized, O> FlexWith<'a, T, O> {
+    /// Converts the contents to another type, preserving the variant.
+    ///
+    /// The `lend` closure projects the borrowed reference and the `give`
+    /// closure converts the owner. Neither copies data unless the closure
+    /// itself does.
+    ///
+    /// Closures such as `|r| r as &[u8]` and `|o| o as Box<[u8]>` perform an
+    /// unsizing coercion, which [`flex_unsize!`](crate::flex_unsize) does for
+    /// you when the owner supports it and the target type is known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flex::Flex;
+    ///
+    /// # #[cfg(feature = "alloc")] {
+    /// let owned: Flex<str> = Flex::Give("  hello  ".into());
+    /// let bytes: Flex<[u8]> = owned.map(str::as_bytes, str::into_boxed_bytes);
+    /// assert!(matches!(bytes, Flex::Give(_)));
+    /// assert_eq!(&*bytes, b"  hello  ");
+    /// # }
+    /// ```
+    pub fn map<U: ?Sized, P, F, G>(self, lend: F, give: G) -> FlexWith<'a, U, P>
+    where
+        F: FnOnce(&'a T) -> &'a U,
+        G: FnOnce(O) -> P,
+    {
+        match self {
+            FlexWith::Lend(r) => FlexWith::Lend(lend(r)),
+            FlexWith::Give(o) => FlexWith::Give(give(o)),
+        }
+    }
+
+    /// Converts the contents to another type, preserving the variant, or
+    /// fails with the closure's error.
+    ///
+    /// This is the fallible form of [`map`](FlexWith::map), for example to
+    /// validate bytes as UTF-8 without copying them in either variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::str::Utf8Error;
+    /// use flex::Flex;
+    ///
+    /// # #[cfg(feature = "alloc")] {
+    /// fn to_str(bytes: Flex<[u8]>) -> Result<Flex<str>, Utf8Error> {
+    ///     bytes.try_map(core::str::from_utf8, |b| {
+    ///         String::from_utf8(b.into_vec())
+    ///             .map(String::into_boxed_str)
+    ///             .map_err(|e| e.utf8_error())
+    ///     })
+    /// }
+    ///
+    /// let text = to_str(Flex::Give(b"hello".to_vec().into_boxed_slice())).unwrap();
+    /// assert_eq!(&*text, "hello");
+    /// assert!(to_str(Flex::Lend(&[0xff])).is_err());
+    /// # }
+    /// ```
+    pub fn try_map<U: ?Sized, P, E, F, G>(self, lend: F, give: G) -> Result<FlexWith<'a, U, P>, E>
+    where
+        F: FnOnce(&'a T) -> Result<&'a U, E>,
+        G: FnOnce(O) -> Result<P, E>,
+    {
+        Ok(match self {
+            FlexWith::Lend(r) => FlexWith::Lend(lend(r)?),
+            FlexWith::Give(o) => FlexWith::Give(give(o)?),
+        })
+    }
+}
+
+impl<'a, T: ?Sized, O: Deref<Target = T>> FlexWith<'a, T, O> {
+    /// Projects the contents with a single closure, claiming the result if
+    /// it is owned.
+    ///
+    /// A `Lend` is projected without copying. A `Give` is projected through
+    /// its owner and the projected data is then copied into a new owner, as
+    /// an owner such as `Box<T>` generally cannot be split into a smaller
+    /// one. This makes it possible to pull one part out of a `Flex` without
+    /// writing a closure for each variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flex::Flex;
+    ///
+    /// struct Header {
+    ///     name: Box<str>,
+    ///     size: u64,
+    /// }
+    ///
+    /// # #[cfg(feature = "alloc")] {
+    /// let header = Flex::Give(Box::new(Header { name: "data".into(), size: 4 }));
+    /// let name: Flex<str> = header.map_or_claim(|h| &*h.name);
+    /// assert!(matches!(name, Flex::Give(_)));
+    /// assert_eq!(&*name, "data");
+    /// # }
+    /// ```
+    pub fn map_or_claim<U: ?Sized, P, F>(self, f: F) -> FlexWith<'a, U, P>
+    where
+        F: FnOnce(&T) -> &U,
+        P: for<'x> From<&'x U>,
+    {
+        match self {
+            FlexWith::Lend(r) => FlexWith::Lend(f(r)),
+            FlexWith::Give(o) => FlexWith::Give(P::from(f(&o))),
+        }
+    }
+}
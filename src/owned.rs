@@ -0,0 +1,260 @@
+//! Converting borrowed data structures into owned ones.
+//!
+//! This module provides the [`IntoOwned`] trait, which converts a value
+//! holding `Flex` fields into one that no longer borrows, typically turning
+//! a `Message<'a>` into a `Message<'static>`. With the `macros` feature,
+//! `#[derive(IntoOwned)]` implements it for your own structs and enums.
+
+use core::ops::Deref;
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    string::String,
+    vec::Vec,
+};
+
+#[cfg(feature = "std")]
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasher, Hash},
+};
+
+use super::FlexWith;
+
+#[cfg(feature = "alloc")]
+use super::FlexMut;
+
+/// Types that can be converted into a version that does not borrow.
+///
+/// For [`FlexWith`] this delegates to [`claim`](FlexWith::claim), and for
+/// containers it converts every element. Types without borrowed data, such
+/// as integers and `String`, are returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use flex::{Flex, IntoOwned};
+///
+/// let text = String::from("hello");
+/// let pair = (Flex::from(text.as_str()), Some(Flex::from(&[1, 2][..])));
+/// let owned: (Flex<'static, str>, Option<Flex<'static, [i32]>>) = pair.into_owned();
+/// drop(text);
+/// assert_eq!(&*owned.0, "hello");
+/// # }
+/// ```
+pub trait IntoOwned {
+    /// The owned version of `Self`, usually `Self` with `'static` lifetimes.
+    type Owned;
+
+    /// Converts `self` into its owned version.
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl<'a, T: ?Sized + 'static, O: Deref<Target = T> + From<&'a T>> IntoOwned for FlexWith<'a, T, O> {
+    type Owned = FlexWith<'static, T, O>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.claim()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T: ?Sized + 'static> IntoOwned for FlexMut<'a, T>
+where
+    Box<T>: From<&'a T>,
+{
+    type Owned = FlexMut<'static, T>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.claim()
+    }
+}
+
+macro_rules! unchanged {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl IntoOwned for $ty {
+                type Owned = Self;
+
+                fn into_owned(self) -> Self::Owned {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+unchanged!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+);
+
+#[cfg(feature = "alloc")]
+unchanged!(String);
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(T::into_owned)
+    }
+}
+
+impl<T: IntoOwned, E: IntoOwned> IntoOwned for Result<T, E> {
+    type Owned = Result<T::Owned, E::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(T::into_owned).map_err(E::into_owned)
+    }
+}
+
+impl<T: IntoOwned, const N: usize> IntoOwned for [T; N] {
+    type Owned = [T::Owned; N];
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(T::into_owned)
+    }
+}
+
+macro_rules! tuple {
+    ($($name:ident)+) => {
+        impl<$($name: IntoOwned),+> IntoOwned for ($($name,)+) {
+            type Owned = ($($name::Owned,)+);
+
+            #[allow(non_snake_case)]
+            fn into_owned(self) -> Self::Owned {
+                let ($($name,)+) = self;
+                ($($name.into_owned(),)+)
+            }
+        }
+    };
+}
+
+tuple!(A);
+tuple!(A B);
+tuple!(A B C);
+tuple!(A B C D);
+tuple!(A B C D E);
+tuple!(A B C D E F);
+tuple!(A B C D E F G);
+tuple!(A B C D E F G H);
+tuple!(A B C D E F G H I);
+tuple!(A B C D E F G H I J);
+tuple!(A B C D E F G H I J K);
+tuple!(A B C D E F G H I J K L);
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T: IntoOwned> IntoOwned for Box<T> {
+    type Owned = Box<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        Box::new((*self).into_owned())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T: IntoOwned> IntoOwned for Vec<T> {
+    type Owned = Vec<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(T::into_owned).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T: IntoOwned> IntoOwned for VecDeque<T> {
+    type Owned = VecDeque<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(T::into_owned).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, B: ?Sized + ToOwned + 'static> IntoOwned for Cow<'a, B> {
+    type Owned = Cow<'static, B>;
+
+    fn into_owned(self) -> Cow<'static, B> {
+        Cow::Owned(Cow::into_owned(self))
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<K: IntoOwned, V: IntoOwned> IntoOwned for BTreeMap<K, V>
+where
+    K::Owned: Ord,
+{
+    type Owned = BTreeMap<K::Owned, V::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T: IntoOwned> IntoOwned for BTreeSet<T>
+where
+    T::Owned: Ord,
+{
+    type Owned = BTreeSet<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(T::into_owned).collect()
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<K: IntoOwned, V: IntoOwned, S: BuildHasher + Default> IntoOwned for HashMap<K, V, S>
+where
+    K::Owned: Eq + Hash,
+{
+    type Owned = HashMap<K::Owned, V::Owned, S>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect()
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<T: IntoOwned, S: BuildHasher + Default> IntoOwned for HashSet<T, S>
+where
+    T::Owned: Eq + Hash,
+{
+    type Owned = HashSet<T::Owned, S>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(T::into_owned).collect()
+    }
+}
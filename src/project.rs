@@ -0,0 +1,338 @@
+//! Borrowed views that live alongside the `Flex` they borrow from.
+//!
+//! A view such as a `&str` field of an owned struct cannot be stored next to
+//! the `Flex` it points into without a self-referential struct.
+//! [`FlexProjected`] is that struct, in the style of the
+//! [`yoke`](https://docs.rs/yoke) crate: it keeps the `Flex` as its cart and
+//! only ever hands out the view with a lifetime bound to itself.
+//!
+//! The view type is written with `'static` lifetimes, which stand in for the
+//! lifetime of the cart, and must implement [`Projectable`] to convert
+//! between the two.
+
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::ptr::NonNull;
+
+use super::{Flex, FlexWith};
+
+/// Types whose lifetime can stand in for the lifetime of a cart.
+///
+/// `Projectable<'b>` is implemented on the `'static` version of a borrowing
+/// type, with `Output` being the same type with its lifetime shortened to
+/// `'b`. For example, `&'static str` implements `Projectable<'b>` with
+/// `Output = &'b str`. It is implemented for references, [`FlexWith`],
+/// `Option` and tuples of up to four elements.
+///
+/// # Safety
+///
+/// This trait may only be implemented for types that are covariant in the
+/// lifetime being replaced, and `Output` must be exactly `Self` with that
+/// lifetime replaced by `'b`.
+pub unsafe trait Projectable<'b>: 'static {
+    /// `Self` with its `'static` lifetime replaced by `'b`.
+    type Output: 'b;
+
+    /// Shortens the lifetime of a borrowed view.
+    fn transform(&'b self) -> &'b Self::Output;
+
+    /// Shortens the lifetime of an owned view.
+    fn transform_owned(self) -> Self::Output;
+
+    /// Extends the lifetime of a view to `'static`.
+    ///
+    /// # Safety
+    ///
+    /// The returned value must not be used after the data it borrows from
+    /// is gone.
+    unsafe fn make(from: Self::Output) -> Self;
+}
+
+unsafe impl<'b, T: ?Sized + 'static> Projectable<'b> for &'static T {
+    type Output = &'b T;
+
+    fn transform(&'b self) -> &'b Self::Output {
+        self
+    }
+
+    fn transform_owned(self) -> Self::Output {
+        self
+    }
+
+    unsafe fn make(from: Self::Output) -> Self {
+        &*(from as *const T)
+    }
+}
+
+unsafe impl<'b, T: ?Sized + 'static, O: 'static> Projectable<'b> for FlexWith<'static, T, O> {
+    type Output = FlexWith<'b, T, O>;
+
+    fn transform(&'b self) -> &'b Self::Output {
+        self
+    }
+
+    fn transform_owned(self) -> Self::Output {
+        self
+    }
+
+    unsafe fn make(from: Self::Output) -> Self {
+        match from {
+            FlexWith::Lend(r) => FlexWith::Lend(&*(r as *const T)),
+            FlexWith::Give(o) => FlexWith::Give(o),
+        }
+    }
+}
+
+unsafe impl<'b, Y: Projectable<'b>> Projectable<'b> for Option<Y> {
+    type Output = Option<Y::Output>;
+
+    fn transform(&'b self) -> &'b Self::Output {
+        // SAFETY: `Y::Output` is `Y` with a shorter lifetime, which has the
+        // same layout, and `Y` is covariant in that lifetime.
+        unsafe { &*(self as *const Self as *const Self::Output) }
+    }
+
+    fn transform_owned(self) -> Self::Output {
+        self.map(Y::transform_owned)
+    }
+
+    unsafe fn make(from: Self::Output) -> Self {
+        from.map(|y| Y::make(y))
+    }
+}
+
+macro_rules! tuple {
+    ($($name:ident)+) => {
+        unsafe impl<'b, $($name: Projectable<'b>),+> Projectable<'b> for ($($name,)+) {
+            type Output = ($($name::Output,)+);
+
+            fn transform(&'b self) -> &'b Self::Output {
+                // SAFETY: See the implementation for `Option`.
+                unsafe { &*(self as *const Self as *const Self::Output) }
+            }
+
+            #[allow(non_snake_case)]
+            fn transform_owned(self) -> Self::Output {
+                let ($($name,)+) = self;
+                ($($name.transform_owned(),)+)
+            }
+
+            #[allow(non_snake_case)]
+            unsafe fn make(from: Self::Output) -> Self {
+                let ($($name,)+) = from;
+                ($($name::make($name),)+)
+            }
+        }
+    };
+}
+
+tuple!(A);
+tuple!(A B);
+tuple!(A B C);
+tuple!(A B C D);
+
+// The cart of a `FlexProjected`.
+//
+// An owned cart is kept as a raw pointer rather than a `Box`, since moving a
+// `Box` asserts unique access to its contents, which the view aliases.
+enum Cart<'a, T: ?Sized> {
+    Lend(&'a T),
+
+    #[cfg(feature = "alloc")]
+    Give(NonNull<T>),
+}
+
+impl<'a, T: ?Sized> Cart<'a, T> {
+    fn new(flex: Flex<'a, T>) -> Self {
+        match flex {
+            FlexWith::Lend(r) => Cart::Lend(r),
+
+            #[cfg(feature = "alloc")]
+            FlexWith::Give(b) => Cart::Give(NonNull::from(Box::leak(b))),
+
+            #[cfg(not(feature = "alloc"))]
+            FlexWith::Give(o) => o.never(),
+        }
+    }
+
+    // Returns the data with an unbounded lifetime, for building views.
+    unsafe fn data<'b>(&self) -> &'b T {
+        match *self {
+            Cart::Lend(r) => &*(r as *const T),
+
+            #[cfg(feature = "alloc")]
+            Cart::Give(p) => &*p.as_ptr(),
+        }
+    }
+
+    fn into_flex(self) -> Flex<'a, T> {
+        let this = ManuallyDrop::new(self);
+        match *this {
+            Cart::Lend(r) => FlexWith::Lend(r),
+
+            // SAFETY: The pointer came from a `Box` in `new`, and `this`
+            // will not free it again.
+            #[cfg(feature = "alloc")]
+            Cart::Give(p) => FlexWith::Give(unsafe { Box::from_raw(p.as_ptr()) }),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Drop for Cart<'a, T> {
+    fn drop(&mut self) {
+        #[cfg(feature = "alloc")]
+        if let Cart::Give(p) = *self {
+            // SAFETY: The pointer came from a `Box` in `new`.
+            drop(unsafe { Box::from_raw(p.as_ptr()) });
+        }
+    }
+}
+
+/// A [`Flex`] together with a view borrowing from its contents.
+///
+/// `FlexProjected<'a, T, Y>` owns a `Flex<'a, T>`, the cart, and a view of
+/// type `Y` that may borrow from it. `Y` is written with `'static` in place
+/// of the borrow, as in `FlexProjected<str, &'static str>`, and
+/// [`get`](FlexProjected::get) shortens it to the lifetime of the
+/// `FlexProjected` again, so the view can never outlive the cart.
+///
+/// This works for both variants: a `Lend` cart borrows for `'a`, and a
+/// `Give` cart keeps its box alive and in place for as long as the view
+/// exists.
+///
+/// # Examples
+///
+/// ```
+/// use flex::{Flex, FlexProjected};
+///
+/// # #[cfg(feature = "alloc")] {
+/// let line: Flex<str> = Flex::Give("name=flex".into());
+///
+/// let pair: FlexProjected<str, (&'static str, &'static str)> =
+///     FlexProjected::project(line, |s| s.split_once('=').unwrap());
+/// assert_eq!(pair.get().0, "name");
+///
+/// let value: FlexProjected<str, &'static str> = pair.map_project(|(_, v), _| v);
+/// assert_eq!(*value.get(), "flex");
+/// assert_eq!(value.cart(), "name=flex");
+/// # }
+/// ```
+pub struct FlexProjected<'a, T: ?Sized, Y: for<'b> Projectable<'b>> {
+    view: ManuallyDrop<Y>,
+    cart: Cart<'a, T>,
+    marker: PhantomData<Flex<'a, T>>,
+}
+
+// SAFETY: The cart is a `&'a T` or a `Box<T>`.
+unsafe impl<'a, T, Y> Send for FlexProjected<'a, T, Y>
+where
+    T: ?Sized + Send + Sync,
+    Y: for<'b> Projectable<'b> + Send,
+{
+}
+
+// SAFETY: The cart is a `&'a T` or a `Box<T>`.
+unsafe impl<'a, T, Y> Sync for FlexProjected<'a, T, Y>
+where
+    T: ?Sized + Sync,
+    Y: for<'b> Projectable<'b> + Sync,
+{
+}
+
+impl<'a, T: ?Sized, Y: for<'b> Projectable<'b>> FlexProjected<'a, T, Y> {
+    /// Creates a `FlexProjected` from a cart and a function building the view.
+    pub fn project<F>(flex: Flex<'a, T>, f: F) -> Self
+    where
+        F: for<'b> FnOnce(&'b T) -> <Y as Projectable<'b>>::Output,
+    {
+        let cart = Cart::new(flex);
+
+        // SAFETY: The data stays in place until the cart is dropped, which
+        // only happens after the view has been dropped.
+        let view = unsafe { Y::make(f(cart.data())) };
+
+        Self {
+            view: ManuallyDrop::new(view),
+            cart,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the view, borrowing from `self`.
+    pub fn get<'b>(&'b self) -> &'b <Y as Projectable<'b>>::Output {
+        self.view.transform()
+    }
+
+    /// Returns the data in the cart.
+    pub fn cart(&self) -> &T {
+        // SAFETY: The result borrows from `self`, which owns the cart.
+        unsafe { self.cart.data() }
+    }
+
+    /// Drops the view and returns the cart.
+    pub fn into_cart(self) -> Flex<'a, T> {
+        self.into_parts().1.into_flex()
+    }
+
+    /// Converts the view into another one, keeping the cart.
+    ///
+    /// The `PhantomData` argument names the lifetime of the view, which
+    /// helps type inference with some closures.
+    pub fn map_project<Z, F>(self, f: F) -> FlexProjected<'a, T, Z>
+    where
+        Z: for<'b> Projectable<'b>,
+        F: for<'b> FnOnce(
+            <Y as Projectable<'b>>::Output,
+            PhantomData<&'b ()>,
+        ) -> <Z as Projectable<'b>>::Output,
+    {
+        let (view, cart) = self.into_parts();
+
+        // SAFETY: The new view borrows from the same cart, which stays in
+        // place.
+        let view = unsafe { Z::make(f(view.transform_owned(), PhantomData)) };
+
+        FlexProjected {
+            view: ManuallyDrop::new(view),
+            cart,
+            marker: PhantomData,
+        }
+    }
+
+    fn into_parts(self) -> (Y, Cart<'a, T>) {
+        let mut this = ManuallyDrop::new(self);
+
+        // SAFETY: `this` is never used or dropped again, so each field is
+        // moved out exactly once.
+        unsafe {
+            let view = ManuallyDrop::take(&mut this.view);
+            (view, core::ptr::read(&this.cart))
+        }
+    }
+}
+
+impl<'a, T: ?Sized, Y: for<'b> Projectable<'b>> Drop for FlexProjected<'a, T, Y> {
+    fn drop(&mut self) {
+        // SAFETY: The view is dropped before the cart it borrows from, and
+        // never used again.
+        unsafe { ManuallyDrop::drop(&mut self.view) }
+    }
+}
+
+impl<'a, T, Y> core::fmt::Debug for FlexProjected<'a, T, Y>
+where
+    T: ?Sized + core::fmt::Debug,
+    Y: for<'b> Projectable<'b>,
+    for<'b> <Y as Projectable<'b>>::Output: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FlexProjected")
+            .field("cart", &self.cart())
+            .field("view", self.get())
+            .finish()
+    }
+}
@@ -0,0 +1,152 @@
+//! Borrowed views of data structures.
+//!
+//! This module provides the [`Reborrow`] trait, which borrows a value
+//! holding `Flex` fields as a cheap view of the same type, with every `Flex`
+//! turned into a `Lend` pointing into the original. With the `macros`
+//! feature, `#[derive(Reborrow)]` implements it for your own structs and
+//! enums.
+
+use core::ops::Deref;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use super::FlexWith;
+
+/// Types that can be borrowed as a view with the shorter lifetime `'b`.
+///
+/// For [`FlexWith`] this delegates to [`reborrow`](FlexWith::reborrow),
+/// which never copies. Containers reborrow every element, and `Copy` types
+/// such as integers are copied.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use flex::{Flex, Reborrow};
+///
+/// let owned: (Flex<'static, str>, Option<Flex<'static, str>>) =
+///     (Flex::Give("a".into()), Some(Flex::Give("b".into())));
+///
+/// let view: (Flex<'_, str>, Option<Flex<'_, str>>) = owned.reborrow();
+/// assert!(matches!(view.1, Some(Flex::Lend("b"))));
+/// # }
+/// ```
+pub trait Reborrow<'b> {
+    /// The borrowed view of `Self`, usually `Self` with lifetime `'b`.
+    type Reborrowed;
+
+    /// Borrows `self` as a view.
+    fn reborrow(&'b self) -> Self::Reborrowed;
+}
+
+impl<'b, 'a: 'b, T: ?Sized, O: Deref<Target = T>> Reborrow<'b> for FlexWith<'a, T, O> {
+    type Reborrowed = FlexWith<'b, T, O>;
+
+    fn reborrow(&'b self) -> Self::Reborrowed {
+        FlexWith::reborrow(self)
+    }
+}
+
+impl<'b, 'a: 'b, T: ?Sized> Reborrow<'b> for &'a T {
+    type Reborrowed = &'b T;
+
+    fn reborrow(&'b self) -> Self::Reborrowed {
+        self
+    }
+}
+
+macro_rules! copied {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl<'b> Reborrow<'b> for $ty {
+                type Reborrowed = Self;
+
+                fn reborrow(&'b self) -> Self::Reborrowed {
+                    *self
+                }
+            }
+        )*
+    };
+}
+
+copied!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+);
+
+impl<'b, T: Reborrow<'b>> Reborrow<'b> for Option<T> {
+    type Reborrowed = Option<T::Reborrowed>;
+
+    fn reborrow(&'b self) -> Self::Reborrowed {
+        self.as_ref().map(T::reborrow)
+    }
+}
+
+impl<'b, T: Reborrow<'b>, E: Reborrow<'b>> Reborrow<'b> for Result<T, E> {
+    type Reborrowed = Result<T::Reborrowed, E::Reborrowed>;
+
+    fn reborrow(&'b self) -> Self::Reborrowed {
+        self.as_ref().map(T::reborrow).map_err(E::reborrow)
+    }
+}
+
+impl<'b, T: Reborrow<'b>, const N: usize> Reborrow<'b> for [T; N] {
+    type Reborrowed = [T::Reborrowed; N];
+
+    fn reborrow(&'b self) -> Self::Reborrowed {
+        self.each_ref().map(T::reborrow)
+    }
+}
+
+macro_rules! tuple {
+    ($($name:ident)+) => {
+        impl<'b, $($name: Reborrow<'b>),+> Reborrow<'b> for ($($name,)+) {
+            type Reborrowed = ($($name::Reborrowed,)+);
+
+            #[allow(non_snake_case)]
+            fn reborrow(&'b self) -> Self::Reborrowed {
+                let ($($name,)+) = self;
+                ($($name.reborrow(),)+)
+            }
+        }
+    };
+}
+
+tuple!(A);
+tuple!(A B);
+tuple!(A B C);
+tuple!(A B C D);
+tuple!(A B C D E);
+tuple!(A B C D E F);
+tuple!(A B C D E F G);
+tuple!(A B C D E F G H);
+tuple!(A B C D E F G H I);
+tuple!(A B C D E F G H I J);
+tuple!(A B C D E F G H I J K);
+tuple!(A B C D E F G H I J K L);
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'b, T: Reborrow<'b>> Reborrow<'b> for Vec<T> {
+    type Reborrowed = Vec<T::Reborrowed>;
+
+    fn reborrow(&'b self) -> Self::Reborrowed {
+        self.iter().map(T::reborrow).collect()
+    }
+}
@@ -0,0 +1,240 @@
+//! Serde support for `FlexWith`.
+//!
+//! Serialization always delegates to the underlying `T`. Deserialization
+//! always yields an owned `Give`, like it does for a `Cow`, so a
+//! `Flex<'static, str>` can be deserialized from any input, including a
+//! streaming reader. Sized values are deserialized into their owner.
+//!
+//! To borrow a `str` or `[u8]` from the input instead, deserialize the field
+//! with [`borrow`], which yields a `Lend` when the deserializer allows it and
+//! falls back to a `Give` otherwise (escaped strings, streaming readers).
+//!
+//! A blanket implementation for other unsized types would overlap the ones
+//! for `str` and `[u8]`, so `FlexWith<'a, [T], O>` and similar types do not
+//! implement `Deserialize`. Use
+//! [`deserialize_give`](FlexWith::deserialize_give) with
+//! `#[serde(deserialize_with = "...")]` for those instead.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use super::FlexWith;
+
+/// Owners that a `FlexWith<'a, [u8], O>` can be deserialized into.
+///
+/// This is any `O: From<&[u8]>`. With the `alloc` feature, `O` must also
+/// implement `From<Vec<u8>>`, so that bytes collected from a sequence are
+/// moved into the owner rather than copied again.
+#[cfg(feature = "alloc")]
+pub trait BytesOwner: for<'x> From<&'x [u8]> + From<Vec<u8>> {}
+
+#[cfg(feature = "alloc")]
+impl<O: for<'x> From<&'x [u8]> + From<Vec<u8>>> BytesOwner for O {}
+
+/// Owners that a `FlexWith<'a, [u8], O>` can be deserialized into.
+#[cfg(not(feature = "alloc"))]
+pub trait BytesOwner: for<'x> From<&'x [u8]> {}
+
+#[cfg(not(feature = "alloc"))]
+impl<O: for<'x> From<&'x [u8]>> BytesOwner for O {}
+
+impl<'a, T: ?Sized + Serialize, O: Deref<Target = T>> Serialize for FlexWith<'a, T, O> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+// Deserializes a string into a `Give`.
+struct StrVisitor<O>(PhantomData<O>);
+
+impl<'de, O: for<'x> From<&'x str>> Visitor<'de> for StrVisitor<O> {
+    type Value = FlexWith<'static, str, O>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(FlexWith::Give(O::from(v)))
+    }
+}
+
+// Deserializes a string into a `Lend` when it can be borrowed from the input.
+struct BorrowedStrVisitor<O>(PhantomData<O>);
+
+impl<'de, O: for<'x> From<&'x str>> Visitor<'de> for BorrowedStrVisitor<O> {
+    type Value = FlexWith<'de, str, O>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(FlexWith::Lend(v))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        StrVisitor(PhantomData).visit_str(v)
+    }
+}
+
+// Deserializes bytes into a `Give`.
+struct BytesVisitor<O>(PhantomData<O>);
+
+impl<'de, O: BytesOwner> Visitor<'de> for BytesVisitor<O> {
+    type Value = FlexWith<'static, [u8], O>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(FlexWith::Give(O::from(v)))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+
+        Ok(FlexWith::Give(O::from(bytes)))
+    }
+}
+
+// Deserializes bytes into a `Lend` when they can be borrowed from the input.
+struct BorrowedBytesVisitor<O>(PhantomData<O>);
+
+impl<'de, O: BytesOwner> Visitor<'de> for BorrowedBytesVisitor<O> {
+    type Value = FlexWith<'de, [u8], O>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a byte array")
+    }
+
+    fn visit_borrowed_bytes<E: Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        Ok(FlexWith::Lend(v))
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        BytesVisitor(PhantomData).visit_bytes(v)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        BytesVisitor(PhantomData).visit_seq(seq)
+    }
+}
+
+impl<'de, 'a, O: for<'x> From<&'x str>> Deserialize<'de> for FlexWith<'a, str, O> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StrVisitor(PhantomData))
+    }
+}
+
+impl<'de, 'a, O: BytesOwner> Deserialize<'de> for FlexWith<'a, [u8], O> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+    }
+}
+
+/// Types that [`borrow`] can deserialize.
+///
+/// This is implemented for `FlexWith<'a, str, O>` and `FlexWith<'a, [u8], O>`
+/// whenever the input outlives `'a`.
+pub trait BorrowDeserialize<'de>: Sized {
+    /// Deserializes `Self`, borrowing from the input when possible.
+    fn borrow_deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+impl<'de: 'a, 'a, O: for<'x> From<&'x str>> BorrowDeserialize<'de> for FlexWith<'a, str, O> {
+    fn borrow_deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(BorrowedStrVisitor(PhantomData))
+    }
+}
+
+impl<'de: 'a, 'a, O: BytesOwner> BorrowDeserialize<'de> for FlexWith<'a, [u8], O> {
+    fn borrow_deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(BorrowedBytesVisitor(PhantomData))
+    }
+}
+
+/// Deserializes a `str` or `[u8]` that borrows from the input when possible.
+///
+/// The input is borrowed as a `Lend` when the deserializer allows it, and
+/// copied into a `Give` otherwise, for example for an escaped JSON string or
+/// a streaming reader. Use it with `#[serde(borrow)]` on the field, just as
+/// serde needs for a borrowed `Cow`.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use flex::Flex;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Message<'a> {
+///     #[serde(borrow, deserialize_with = "flex::serde::borrow")]
+///     name: Flex<'a, str>,
+/// }
+///
+/// let message: Message = serde_json::from_str(r#"{"name":"flex"}"#).unwrap();
+/// assert!(matches!(message.name, Flex::Lend("flex")));
+/// # }
+/// ```
+pub fn borrow<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: BorrowDeserialize<'de>,
+{
+    T::borrow_deserialize(deserializer)
+}
+
+impl<'de, 'a, T, O: Deserialize<'de>> Deserialize<'de> for FlexWith<'a, T, O> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::deserialize_give(deserializer)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'a, T: ?Sized, O> FlexWith<'a, T, O> {
+    /// Deserializes a `Give` through the owner's `Deserialize`.
+    ///
+    /// This is how sized values are deserialized. It also works for unsized
+    /// types other than `str` and `[u8]`, which do not implement
+    /// `Deserialize` themselves, through `#[serde(deserialize_with)]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use flex::Flex;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Samples {
+    ///     #[serde(deserialize_with = "Flex::deserialize_give")]
+    ///     values: Flex<'static, [u32]>,
+    /// }
+    ///
+    /// let samples: Samples = serde_json::from_str(r#"{"values":[1,2]}"#).unwrap();
+    /// assert_eq!(&*samples.values, &[1, 2]);
+    /// # }
+    /// ```
+    pub fn deserialize_give<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        O: Deserialize<'de>,
+    {
+        O::deserialize(deserializer).map(FlexWith::Give)
+    }
+}
@@ -0,0 +1,114 @@
+//! Shared ownership aliases for `FlexWith`.
+//!
+//! This module provides [`FlexRc`] and [`FlexArc`], which hold either a
+//! borrowed reference or a reference-counted pointer. Cloning a shared
+//! variant bumps the reference count rather than cloning the data, and an
+//! owned [`Flex`] can be moved into a shared one without cloning the data.
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+
+use super::{Flex, FlexWith};
+
+/// A flexible container that can hold either a borrowed reference or a
+/// single-threaded shared pointer.
+///
+/// See the [`FlexArc`] documentation for details; `FlexRc` is identical
+/// except that it is backed by [`Rc`] instead of [`Arc`].
+///
+/// # Examples
+///
+/// ```
+/// use flex::FlexRc;
+/// use std::rc::Rc;
+///
+/// let shared: FlexRc<str> = FlexRc::from(Rc::from("hello"));
+/// let clone = shared.clone();
+/// assert_eq!(&*clone, "hello");
+/// ```
+pub type FlexRc<'a, T> = FlexWith<'a, T, Rc<T>>;
+
+/// A flexible container that can hold either a borrowed reference or a
+/// thread-safe shared pointer.
+///
+/// `FlexArc` is useful when values are already behind an [`Arc`]: unlike
+/// [`Flex`], which would require a deep copy into a `Box<T>`, `FlexArc`
+/// stores the `Arc<T>` directly and cloning it only bumps the reference
+/// count.
+///
+/// # Variants
+///
+/// - [`Lend`](FlexWith::Lend): Holds a borrowed reference `&'a T`
+/// - [`Give`](FlexWith::Give): Holds a shared `Arc<T>`
+///
+/// # Examples
+///
+/// ```
+/// use flex::{Flex, FlexArc};
+/// use std::sync::Arc;
+///
+/// let arc: Arc<str> = Arc::from("hello");
+/// let shared = FlexArc::from(arc.clone());
+/// let clone = shared.clone();
+/// assert_eq!(Arc::strong_count(&arc), 3);
+/// assert_eq!(&*clone, "hello");
+///
+/// // An owned `Flex` moves its box into the `Arc` without cloning
+/// let owned = Flex::Give(String::from("world").into_boxed_str());
+/// let shared: FlexArc<'static, str> = FlexArc::from(owned);
+/// assert_eq!(&*shared, "world");
+/// ```
+pub type FlexArc<'a, T> = FlexWith<'a, T, Arc<T>>;
+
+macro_rules! shared {
+    ($name:ident, $ptr:ident, $into:ident) => {
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl<'a, T: ?Sized> $name<'a, T>
+        where
+            $ptr<T>: From<&'a T>,
+        {
+            #[doc = concat!("Converts into an `", stringify!($ptr), "<T>`, consuming `self`.")]
+            ///
+            /// For `Lend` variants, this allocates a new pointer from the
+            /// borrowed reference. For `Give` variants, this simply returns
+            /// the existing pointer.
+            pub fn $into(self) -> $ptr<T> {
+                self.into_owner()
+            }
+        }
+
+        impl<T: ?Sized> From<$ptr<T>> for $name<'_, T> {
+            fn from(p: $ptr<T>) -> Self {
+                FlexWith::Give(p)
+            }
+        }
+
+        impl<T: ?Sized> From<Box<T>> for $name<'_, T> {
+            fn from(b: Box<T>) -> Self {
+                FlexWith::Give(b.into())
+            }
+        }
+
+        impl<'a, T: ?Sized> From<Flex<'a, T>> for $name<'a, T> {
+            fn from(f: Flex<'a, T>) -> Self {
+                match f {
+                    FlexWith::Lend(r) => FlexWith::Lend(r),
+                    FlexWith::Give(b) => FlexWith::Give(b.into()),
+                }
+            }
+        }
+
+        impl<'a, T: ?Sized> From<$name<'a, T>> for $ptr<T>
+        where
+            $ptr<T>: From<&'a T>,
+        {
+            fn from(s: $name<'a, T>) -> Self {
+                s.$into()
+            }
+        }
+    };
+}
+
+shared!(FlexRc, Rc, into_rc);
+shared!(FlexArc, Arc, into_arc);
@@ -0,0 +1,95 @@
+//! Unsizing coercions for `FlexWith`.
+//!
+//! `&T` and `Box<T>` coerce implicitly to `&dyn Trait` and `Box<dyn Trait>`
+//! through the unstable `CoerceUnsized` trait. The compiler only allows
+//! `CoerceUnsized` for structs, so `FlexWith`, being an enum, cannot coerce
+//! implicitly even on nightly. Instead, [`flex_unsize!`](crate::flex_unsize)
+//! and [`flex_upcast!`](crate::flex_upcast) coerce each variant explicitly,
+//! as does [`map`](crate::FlexWith::map) with coercing closures.
+
+/// Performs an unsizing coercion on a `FlexWith`, preserving the variant.
+///
+/// The target type is taken from context, just like an implicit coercion
+/// of `&T` or `Box<T>`. This works with any owner that supports the
+/// coercion itself, such as `Box<T>`, `Rc<T>` and `Arc<T>`. Owners that
+/// cannot coerce, such as [`Unowned`](crate::Unowned) without the `alloc`
+/// feature, can use [`map`](crate::FlexWith::map) instead.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use core::fmt::Debug;
+/// use flex::{flex_unsize, Flex};
+///
+/// let owned = Flex::Give(Box::new([0u8; 32]));
+/// let slice: Flex<[u8]> = flex_unsize!(owned);
+/// assert_eq!(slice.len(), 32);
+///
+/// let value = 42;
+/// let debug: Flex<dyn Debug> = flex_unsize!(Flex::Lend(&value));
+/// assert_eq!(format!("{:?}", debug), "Lend(42)");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! flex_unsize {
+    ($flex:expr) => {
+        match $flex {
+            $crate::FlexWith::Lend(r) => $crate::FlexWith::Lend(r),
+            $crate::FlexWith::Give(o) => $crate::FlexWith::Give(o),
+        }
+    };
+}
+
+/// Upcasts a `Flex<dyn Trait>` to a `Flex<dyn Supertrait>`, preserving the
+/// variant.
+///
+/// A `Lend(&dyn Trait)` becomes a `Lend(&dyn Supertrait)` and a
+/// `Give(Box<dyn Trait>)` becomes a `Give(Box<dyn Supertrait>)`, without
+/// copying or reallocating. The target trait object can be given after
+/// `=>`, or taken from context like [`flex_unsize!`](crate::flex_unsize).
+///
+/// This relies on trait upcasting coercion, which requires Rust 1.86 or
+/// later.
+///
+/// # Examples
+///
+/// ```
+/// use core::any::Any;
+/// use core::fmt::Debug;
+/// use flex::{flex_upcast, Flex};
+///
+/// trait Plugin: Any + Debug {
+///     fn name(&self) -> &str;
+/// }
+///
+/// #[derive(Debug)]
+/// struct Echo;
+///
+/// impl Plugin for Echo {
+///     fn name(&self) -> &str {
+///         "echo"
+///     }
+/// }
+///
+/// # #[rustversion::since(1.86)]
+/// # fn main() {
+/// # #[cfg(feature = "alloc")] {
+/// let plugin: Flex<dyn Plugin> = Flex::Give(Box::new(Echo));
+/// let any = flex_upcast!(plugin => dyn Any);
+/// assert!(any.downcast_ref::<Echo>().is_some());
+/// # }
+/// # }
+/// # #[rustversion::before(1.86)]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! flex_upcast {
+    ($flex:expr => $target:ty) => {{
+        let flex: $crate::Flex<'_, $target> = $crate::flex_unsize!($flex);
+        flex
+    }};
+    ($flex:expr) => {
+        $crate::flex_unsize!($flex)
+    };
+}
@@ -0,0 +1,39 @@
+//! Yoke implementations for `FlexWith`.
+//!
+//! `FlexWith<'static, T, O>` implements [`Yokeable`], so a `Flex` can be
+//! the borrowed view of a [`Yoke`](yoke::Yoke), just like a `Cow`.
+
+use yoke::Yokeable;
+
+use super::FlexWith;
+
+// SAFETY: `FlexWith<'a, T, O>` is covariant in `'a`.
+unsafe impl<'b, T: ?Sized + 'static, O: 'static> Yokeable<'b> for FlexWith<'static, T, O> {
+    type Output = FlexWith<'b, T, O>;
+
+    fn transform(&'b self) -> &'b Self::Output {
+        self
+    }
+
+    fn transform_owned(self) -> Self::Output {
+        self
+    }
+
+    unsafe fn make(from: Self::Output) -> Self {
+        match from {
+            FlexWith::Lend(r) => FlexWith::Lend(&*(r as *const T)),
+            FlexWith::Give(o) => FlexWith::Give(o),
+        }
+    }
+
+    fn transform_mut<F>(&'b mut self, f: F)
+    where
+        F: 'static + for<'c> FnOnce(&'c mut Self::Output),
+    {
+        // SAFETY: `f` cannot store the shortened reference anywhere, as it
+        // must work for any lifetime `'c`.
+        #[allow(clippy::unnecessary_cast)] // The lifetimes differ
+        let output = unsafe { &mut *(self as *mut Self as *mut Self::Output) };
+        f(output)
+    }
+}
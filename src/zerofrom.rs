@@ -0,0 +1,31 @@
+//! ZeroFrom implementations for `FlexWith`.
+//!
+//! A `FlexWith<'zf, T, O>` can be created without copying from another
+//! `FlexWith` of either variant, or from a `str` or slice, always yielding a
+//! `Lend` that borrows the source.
+
+use core::ops::Deref;
+
+use zerofrom::ZeroFrom;
+
+use super::FlexWith;
+
+impl<'zf, 's, T: ?Sized, O: Deref<Target = T> + 'zf> ZeroFrom<'zf, FlexWith<'s, T, O>>
+    for FlexWith<'zf, T, O>
+{
+    fn zero_from(other: &'zf FlexWith<'s, T, O>) -> Self {
+        FlexWith::Lend(other)
+    }
+}
+
+impl<'zf, O: 'zf> ZeroFrom<'zf, str> for FlexWith<'zf, str, O> {
+    fn zero_from(other: &'zf str) -> Self {
+        FlexWith::Lend(other)
+    }
+}
+
+impl<'zf, T: 'zf, O: 'zf> ZeroFrom<'zf, [T]> for FlexWith<'zf, [T], O> {
+    fn zero_from(other: &'zf [T]) -> Self {
+        FlexWith::Lend(other)
+    }
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "allocator-api")]
+#![cfg_attr(feature = "nightly", feature(allocator_api))]
+
+use allocator_api2::alloc::{AllocError, Allocator, Global, Layout};
+use allocator_api2::boxed::Box;
+use flex::FlexIn;
+use std::cell::Cell;
+use std::ptr::NonNull;
+
+// An allocator that counts the allocations made through it
+struct Counting<'a>(&'a Cell<usize>);
+
+unsafe impl Allocator for Counting<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.set(self.0.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        Global.deallocate(ptr, layout)
+    }
+}
+
+// Allocator-aware claim tests
+mod claim_in {
+    use super::*;
+
+    #[test]
+    fn lend_slice() {
+        let count = Cell::new(0);
+        let flex: FlexIn<[i32], Counting> = FlexIn::Lend(&[1, 2, 3][..]);
+        let owned: FlexIn<'static, [i32], Counting> = flex.claim_in(Counting(&count));
+        assert!(matches!(owned, FlexIn::Give(_)));
+        assert_eq!(&*owned, &[1, 2, 3]);
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn lend_str() {
+        let count = Cell::new(0);
+        let flex: FlexIn<str, Counting> = FlexIn::Lend("hello");
+        let owned = flex.claim_in(Counting(&count));
+        assert_eq!(&*owned, "hello");
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn lend_sized() {
+        let count = Cell::new(0);
+        let flex: FlexIn<u64, Counting> = FlexIn::Lend(&42);
+        let owned = flex.claim_in(Counting(&count));
+        assert_eq!(*owned, 42);
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn give_does_not_allocate() {
+        let count = Cell::new(0);
+        let boxed = Box::new_in(42, Counting(&count));
+        let flex: FlexIn<u64, Counting> = FlexIn::Give(boxed);
+        let owned = flex.claim_in(Counting(&count));
+        assert_eq!(*owned, 42);
+        assert_eq!(count.get(), 1);
+    }
+}
+
+// Forwarded trait tests
+mod derive {
+    use super::*;
+
+    #[test]
+    fn lend_eq_give() {
+        let lend: FlexIn<str, Global> = FlexIn::Lend("hello");
+        let give: FlexIn<str, Global> = FlexIn::Lend("hello").claim_in(Global);
+        assert_eq!(lend, give);
+        assert_eq!(format!("{}", give), "hello");
+    }
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "alloc")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use flex::{AtomicFlex, FlexArc};
+
+static DEFAULT: &str = "default";
+
+// Counts how often it is dropped
+struct Counted(u32, &'static AtomicUsize);
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        self.1.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+// Loading and storing
+mod basics {
+    use super::*;
+
+    #[test]
+    fn load() {
+        let cell = AtomicFlex::from(DEFAULT);
+        assert!(matches!(cell.load(), FlexArc::Lend("default")));
+    }
+
+    #[test]
+    fn store() {
+        let cell = AtomicFlex::from(DEFAULT);
+        cell.store(FlexArc::Give(Arc::from("owned")));
+        assert!(matches!(cell.load(), FlexArc::Give(ref a) if &**a == "owned"));
+
+        cell.store(FlexArc::Lend(DEFAULT));
+        assert_eq!(&*cell.load(), "default");
+    }
+
+    #[test]
+    fn swap() {
+        let cell: AtomicFlex<str> = AtomicFlex::from(Arc::from("first"));
+        let old = cell.swap(FlexArc::Lend("second"));
+        assert_eq!(&*old, "first");
+        assert_eq!(&*cell.load(), "second");
+    }
+
+    #[test]
+    fn into_inner() {
+        let cell: AtomicFlex<str> = AtomicFlex::from(Arc::from("owned"));
+        assert_eq!(&*cell.into_inner(), "owned");
+    }
+
+    #[test]
+    fn guard_outlives_store() {
+        let arc: Arc<str> = Arc::from("first");
+        let cell = AtomicFlex::from(arc.clone());
+        let guard = cell.load();
+        cell.store(FlexArc::Lend(DEFAULT));
+        assert_eq!(Arc::strong_count(&arc), 2);
+        assert_eq!(&*guard, "first");
+        drop(guard);
+        assert_eq!(Arc::strong_count(&arc), 1);
+    }
+
+    #[test]
+    fn default() {
+        let cell: AtomicFlex<Vec<u8>> = AtomicFlex::default();
+        assert!(cell.load().is_empty());
+    }
+
+    #[test]
+    fn debug() {
+        let cell = AtomicFlex::from(DEFAULT);
+        assert_eq!(format!("{:?}", cell), "AtomicFlex(Lend(\"default\"))");
+    }
+}
+
+// Old values are reclaimed exactly once
+#[test]
+fn drops() {
+    let drops: &'static AtomicUsize = Box::leak(Box::new(AtomicUsize::new(0)));
+
+    let cell = AtomicFlex::from(Arc::new(Counted(1, drops)));
+    cell.store(FlexArc::Give(Arc::new(Counted(2, drops))));
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+
+    let old = cell.swap(FlexArc::Give(Arc::new(Counted(3, drops))));
+    assert_eq!(old.0, 2);
+    drop(old);
+    assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+    drop(cell);
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+}
+
+// Concurrent loads and stores
+#[test]
+fn threads() {
+    let cell = Arc::new(AtomicFlex::from(Arc::new(0usize)));
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                let mut last = 0;
+                for _ in 0..10_000 {
+                    let value = *cell.load();
+                    assert!(value >= last);
+                    last = value;
+                }
+            })
+        })
+        .collect();
+
+    for value in 1..=1_000 {
+        cell.store(FlexArc::Give(Arc::new(value)));
+    }
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    assert_eq!(*cell.load(), 1_000);
+}
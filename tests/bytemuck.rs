@@ -0,0 +1,161 @@
+#![cfg(feature = "bytemuck")]
+
+use bytemuck::{PodCastError, Zeroable};
+use flex::Flex;
+
+#[derive(Clone, Copy, Debug, PartialEq, Zeroable, bytemuck::Pod)]
+#[repr(C)]
+struct Header {
+    magic: u32,
+    size: u32,
+}
+
+// Returns a `u32`-aligned buffer and a misaligned view of its bytes.
+fn misaligned(words: &[u32; 3]) -> &[u8] {
+    &bytemuck::cast_slice(words)[1..9]
+}
+
+// try_cast_slice tests
+mod try_cast_slice {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let words = [1u32, 2];
+        let bytes = Flex::Lend(&words[..]).try_cast_slice::<u8>().unwrap();
+        assert!(matches!(bytes, Flex::Lend(r) if r.as_ptr() == words.as_ptr().cast()));
+
+        let words = bytes.try_cast_slice::<u32>().unwrap();
+        assert!(matches!(words, Flex::Lend(&[1, 2])));
+    }
+
+    #[test]
+    fn slop() {
+        let bytes = [1u8, 2, 3];
+        let error = Flex::Lend(&bytes[..]).try_cast_slice::<u16>().unwrap_err();
+        assert_eq!(
+            error.pod_cast_error(),
+            PodCastError::OutputSliceWouldHaveSlop
+        );
+        assert_eq!(error.as_slice(), &bytes);
+        assert!(matches!(error.into_flex(), Flex::Lend(r) if r == bytes));
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[test]
+    fn misaligned() {
+        let words = [0u32; 3];
+        let error = Flex::Lend(super::misaligned(&words))
+            .try_cast_slice::<u32>()
+            .unwrap_err();
+        assert_eq!(
+            error.pod_cast_error(),
+            PodCastError::TargetAlignmentGreaterAndInputNotAligned
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn copied() {
+        let words = [0x0403_0201u32, 0x0807_0605, 0x0c0b_0a09];
+        let bytes = super::misaligned(&words);
+        let copied = Flex::Lend(bytes).try_cast_slice::<u32>().unwrap();
+        assert!(matches!(copied, Flex::Give(..)));
+        assert_eq!(bytemuck::cast_slice::<u32, u8>(&copied), bytes);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reused() {
+        let words = vec![1u32, 2].into_boxed_slice();
+        let ptr = words.as_ptr();
+        let floats = Flex::Give(words).try_cast_slice::<f32>().unwrap();
+        assert!(matches!(floats, Flex::Give(ref b) if b.as_ptr().cast() == ptr));
+        assert_eq!(floats[0].to_bits(), 1);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reallocated() {
+        let bytes = vec![1u8, 0, 2, 0].into_boxed_slice();
+        let halves = Flex::Give(bytes).try_cast_slice::<u16>().unwrap();
+        assert_eq!(&*halves, &[u16::from_le(1), u16::from_le(2)]);
+
+        let error = Flex::Give(vec![1u8; 3].into_boxed_slice())
+            .try_cast_slice::<u16>()
+            .unwrap_err();
+        assert!(matches!(error.into_flex(), Flex::Give(b) if *b == [1, 1, 1]));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn zst() {
+        let units = Flex::Give(vec![(); 3].into_boxed_slice());
+        let empty = units.try_cast_slice::<[u8; 0]>().unwrap();
+        assert_eq!(empty.len(), 3);
+
+        let bytes = Flex::Give(Box::<[u8]>::default());
+        assert_eq!(bytes.try_cast_slice::<()>().unwrap().len(), 0);
+    }
+}
+
+// cast_slice tests
+mod cast_slice {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let words = [u16::MAX];
+        let bytes: Flex<[u8]> = Flex::Lend(&words[..]).cast_slice();
+        assert_eq!(&*bytes, &[0xff, 0xff]);
+    }
+
+    #[test]
+    #[should_panic(expected = "OutputSliceWouldHaveSlop")]
+    fn slop() {
+        let _: Flex<[u16]> = Flex::Lend(&[0u8][..]).cast_slice();
+    }
+}
+
+// try_cast tests
+mod try_cast {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let words = [7u32, 8];
+        let header = Flex::Lend(&words[..]).try_cast::<Header>().unwrap();
+        assert!(matches!(header, Flex::Lend(h) if h == &Header { magic: 7, size: 8 }));
+    }
+
+    #[test]
+    fn size() {
+        let words = [7u32];
+        let error = Flex::Lend(&words[..]).try_cast::<Header>().unwrap_err();
+        assert_eq!(error.pod_cast_error(), PodCastError::SizeMismatch);
+        assert_eq!(error.to_string(), "SizeMismatch");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give() {
+        let words = vec![7u32, 8].into_boxed_slice();
+        let ptr = words.as_ptr();
+        let header = Flex::Give(words).try_cast::<Header>().unwrap();
+        assert!(matches!(header, Flex::Give(ref h) if (&**h as *const Header).cast() == ptr));
+        assert_eq!(header.size, 8);
+
+        let bytes = bytemuck::bytes_of(&Header { magic: 1, size: 2 }).to_vec();
+        let header: Flex<Header> = Flex::Give(bytes.into_boxed_slice()).cast();
+        assert_eq!(*header, Header { magic: 1, size: 2 });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn copied() {
+        let words = [0u32; 3];
+        let header: Flex<Header> = Flex::Lend(super::misaligned(&words)).cast();
+        assert!(matches!(header, Flex::Give(..)));
+        assert_eq!(*header, Header::zeroed());
+    }
+}
@@ -0,0 +1,170 @@
+#![cfg(feature = "alloc")]
+
+use flex::{clone_box, CloneBox, Flex};
+
+trait Shape: CloneBox {
+    fn area(&self) -> u32;
+}
+
+impl<'a> From<&(dyn Shape + 'a)> for Box<dyn Shape + 'a> {
+    fn from(shape: &(dyn Shape + 'a)) -> Self {
+        clone_box(shape)
+    }
+}
+
+impl<'a> Clone for Box<dyn Shape + 'a> {
+    fn clone(&self) -> Self {
+        clone_box(&**self)
+    }
+}
+
+impl<'a> From<&(dyn Shape + Send + Sync + 'a)> for Box<dyn Shape + Send + Sync + 'a> {
+    fn from(shape: &(dyn Shape + Send + Sync + 'a)) -> Self {
+        clone_box(shape)
+    }
+}
+
+// A trait without `From` or `Clone` implementations for its boxes
+trait Named: CloneBox {
+    fn name(&self) -> &str;
+}
+
+#[derive(Clone)]
+struct Square(u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+impl Named for Square {
+    fn name(&self) -> &str {
+        "square"
+    }
+}
+
+#[derive(Clone)]
+struct Point;
+
+impl Shape for Point {
+    fn area(&self) -> u32 {
+        0
+    }
+}
+
+// clone_box tests
+mod clone_box {
+    use super::*;
+
+    #[test]
+    fn sized() {
+        let boxed: Box<String> = clone_box(&String::from("hello"));
+        assert_eq!(*boxed, "hello");
+    }
+
+    #[test]
+    fn slice() {
+        let strings = [String::from("a"), String::from("b")];
+        let boxed: Box<[String]> = clone_box(&strings[..]);
+        assert_eq!(&*boxed, &strings[..]);
+        assert_ne!(boxed.as_ptr(), strings.as_ptr());
+    }
+
+    #[test]
+    fn empty_slice() {
+        let boxed: Box<[u8]> = clone_box(&[][..]);
+        assert!(boxed.is_empty());
+    }
+
+    #[test]
+    fn str() {
+        let boxed: Box<str> = clone_box("hello");
+        assert_eq!(&*boxed, "hello");
+    }
+
+    #[test]
+    fn trait_object() {
+        let square = Square(3);
+        let shape: &dyn Shape = &square;
+        let boxed = clone_box(shape);
+        assert_eq!(boxed.area(), 9);
+    }
+
+    #[test]
+    fn zero_sized() {
+        let shape: &dyn Shape = &Point;
+        assert_eq!(clone_box(shape).area(), 0);
+    }
+}
+
+// Claiming trait objects
+mod claim {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let square = Square(2);
+        let borrowed: Flex<dyn Shape> = Flex::Lend(&square);
+        let owned: Flex<'static, dyn Shape> = borrowed.claim();
+        assert!(matches!(owned, Flex::Give(..)));
+        assert_eq!(owned.area(), 4);
+    }
+
+    #[test]
+    fn give() {
+        let boxed: Box<dyn Shape> = Box::new(Square(5));
+        let ptr = &*boxed as *const dyn Shape as *const u8;
+        let owned: Flex<'static, dyn Shape> = Flex::Give(boxed).claim();
+        assert_eq!(&*owned as *const dyn Shape as *const u8, ptr);
+    }
+
+    #[test]
+    fn send_sync() {
+        let square = Square(4);
+        let borrowed: Flex<dyn Shape + Send + Sync> = Flex::Lend(&square);
+        let owned: Flex<'static, dyn Shape + Send + Sync> = borrowed.claim();
+        let area = std::thread::spawn(move || owned.area());
+        assert_eq!(area.join().unwrap(), 16);
+    }
+
+    #[test]
+    fn clone() {
+        let owned: Flex<dyn Shape> = Flex::Give(Box::new(Square(3)));
+        let cloned = owned.clone();
+        assert_eq!(cloned.area(), 9);
+        assert!(!std::ptr::eq(
+            &*owned as *const dyn Shape as *const u8,
+            &*cloned as *const dyn Shape as *const u8
+        ));
+    }
+}
+
+// Claiming through CloneBox alone
+mod claim_dyn {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let square = Square(2);
+        let borrowed: Flex<dyn Named> = Flex::Lend(&square);
+        let owned: Flex<'static, dyn Named> = borrowed.claim_dyn();
+        assert!(matches!(owned, Flex::Give(..)));
+        assert_eq!(owned.name(), "square");
+    }
+
+    #[test]
+    fn give() {
+        let boxed: Box<dyn Named> = Box::new(Square(5));
+        let ptr = &*boxed as *const dyn Named as *const u8;
+        let owned = Flex::Give(boxed).into_box_dyn();
+        assert_eq!(&*owned as *const dyn Named as *const u8, ptr);
+    }
+
+    #[test]
+    fn slice() {
+        let strings = [String::from("a")];
+        let owned: Box<[String]> = Flex::Lend(&strings[..]).into_box_dyn();
+        assert_eq!(&*owned, &strings[..]);
+    }
+}
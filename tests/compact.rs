@@ -0,0 +1,210 @@
+#![cfg(feature = "alloc")]
+
+use core::mem::size_of;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use flex::{CompactFlex, Flex};
+
+fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Size matches a plain reference
+#[test]
+fn size() {
+    assert_eq!(size_of::<CompactFlex<str>>(), size_of::<&str>());
+    assert_eq!(size_of::<CompactFlex<[u64]>>(), size_of::<&[u64]>());
+    assert_eq!(
+        size_of::<Option<CompactFlex<str>>>(),
+        size_of::<Option<&str>>()
+    );
+    assert!(size_of::<CompactFlex<str>>() < size_of::<Flex<str>>());
+}
+
+// Lend and Give
+mod variants {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let text = String::from("hello");
+        let compact = CompactFlex::lend(text.as_str());
+        assert!(compact.is_lend());
+        assert!(!compact.is_give());
+        assert_eq!(&*compact, "hello");
+        assert_eq!(compact.as_ptr(), text.as_ptr());
+    }
+
+    #[test]
+    fn give() {
+        let compact: CompactFlex<[u8]> = CompactFlex::give(vec![1, 2, 3].into_boxed_slice());
+        assert!(compact.is_give());
+        assert!(!compact.is_lend());
+        assert_eq!(&*compact, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn empty() {
+        let lend: CompactFlex<str> = CompactFlex::lend("");
+        let give: CompactFlex<str> = CompactFlex::give("".into());
+        assert!(lend.is_lend());
+        assert!(give.is_give());
+        assert_eq!(lend, give);
+        assert!(give.is_empty());
+    }
+
+    #[test]
+    fn zero_sized() {
+        let compact: CompactFlex<[()]> = CompactFlex::give(vec![(); 5].into_boxed_slice());
+        assert!(compact.is_give());
+        assert_eq!(compact.len(), 5);
+    }
+
+    // The length must not be mistaken for an owned slice
+    #[test]
+    #[should_panic(expected = "cannot borrow more than isize::MAX elements")]
+    fn too_long() {
+        let huge: &[()] = &[(); usize::MAX];
+        let _ = CompactFlex::lend(huge);
+    }
+}
+
+// Conversions to and from Flex
+mod conversions {
+    use super::*;
+
+    #[test]
+    fn into_flex() {
+        let lend = CompactFlex::lend("hello").into_flex();
+        assert!(matches!(lend, Flex::Lend("hello")));
+
+        let give: CompactFlex<str> = CompactFlex::give("hello".into());
+        assert!(matches!(give.into_flex(), Flex::Give(b) if &*b == "hello"));
+    }
+
+    #[test]
+    fn from_flex() {
+        let compact = CompactFlex::from(Flex::Lend("hello"));
+        assert!(compact.is_lend());
+
+        let compact: CompactFlex<str> = CompactFlex::from(Flex::Give("hello".into()));
+        assert!(compact.is_give());
+
+        let flex: Flex<str> = compact.into();
+        assert!(matches!(flex, Flex::Give(_)));
+    }
+
+    #[test]
+    fn give_keeps_allocation() {
+        let boxed: Box<[u32]> = vec![1, 2, 3].into_boxed_slice();
+        let ptr = boxed.as_ptr();
+        let compact = CompactFlex::from(boxed);
+        assert_eq!(compact.as_ptr(), ptr);
+        let boxed = compact.into_box();
+        assert_eq!(boxed.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn from_ref() {
+        let compact: CompactFlex<[u8]> = (&[1u8, 2][..]).into();
+        assert!(compact.is_lend());
+    }
+
+    #[test]
+    fn claim() {
+        let text = String::from("hello");
+        let compact = CompactFlex::lend(text.as_str());
+        let owned: CompactFlex<'static, str> = compact.claim();
+        drop(text);
+        assert!(owned.is_give());
+        assert_eq!(owned, "hello");
+    }
+
+    #[test]
+    fn into_box() {
+        let boxed = CompactFlex::lend(&[1, 2, 3][..]).into_box();
+        assert_eq!(&*boxed, &[1, 2, 3]);
+    }
+}
+
+// Derived traits
+mod traits {
+    use super::*;
+
+    #[test]
+    fn clone() {
+        let lend = CompactFlex::lend("hello");
+        let copy = lend.clone();
+        assert!(copy.is_lend());
+        assert_eq!(copy.as_ptr(), lend.as_ptr());
+
+        let give: CompactFlex<str> = CompactFlex::give("hello".into());
+        let copy = give.clone();
+        assert!(copy.is_give());
+        assert_ne!(copy.as_ptr(), give.as_ptr());
+        assert_eq!(copy, give);
+    }
+
+    #[test]
+    fn compare() {
+        let lend = CompactFlex::lend("a");
+        let give: CompactFlex<str> = CompactFlex::give("b".into());
+        assert!(lend < give);
+        assert_eq!(lend.cmp(&give), core::cmp::Ordering::Less);
+        assert_eq!(lend, "a");
+        assert_eq!(give, Box::<str>::from("b"));
+        assert!(give > "a");
+    }
+
+    #[test]
+    fn hashing() {
+        let give: CompactFlex<str> = CompactFlex::give("hello".into());
+        assert_eq!(hash(&give), hash("hello"));
+        assert_eq!(hash(&CompactFlex::lend("hello")), hash("hello"));
+    }
+
+    #[test]
+    fn default() {
+        let compact: CompactFlex<str> = CompactFlex::default();
+        assert!(compact.is_lend());
+        assert!(compact.is_empty());
+    }
+
+    #[test]
+    fn format() {
+        let give: CompactFlex<str> = CompactFlex::give("hi".into());
+        assert_eq!(format!("{:?}", CompactFlex::lend("hi")), "Lend(\"hi\")");
+        assert_eq!(format!("{:?}", give), "Give(\"hi\")");
+        assert_eq!(format!("{}", give), "hi");
+    }
+
+    #[test]
+    fn as_ref() {
+        let compact = CompactFlex::lend("hello");
+        let bytes: &[u8] = compact.as_ref();
+        assert_eq!(bytes, b"hello");
+    }
+}
+
+// Owned data is dropped exactly once
+#[test]
+fn drops() {
+    let rc = Rc::new(());
+    let compact: CompactFlex<[Rc<()>]> = CompactFlex::give(vec![rc.clone(), rc.clone()].into());
+    assert_eq!(Rc::strong_count(&rc), 3);
+
+    let flex = compact.into_flex();
+    assert_eq!(Rc::strong_count(&rc), 3);
+
+    let compact = CompactFlex::from(flex);
+    drop(compact);
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    let items = [rc.clone()];
+    drop(CompactFlex::lend(&items[..]));
+    assert_eq!(Rc::strong_count(&rc), 2);
+}
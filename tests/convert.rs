@@ -125,3 +125,228 @@ mod from {
         assert_eq!(&*cow, &[1, 2, 3]);
     }
 }
+
+// Owned alloc type conversion tests
+#[cfg(feature = "alloc")]
+mod owned {
+    use super::*;
+
+    #[test]
+    fn from_string() {
+        let s = String::from("hello");
+        let ptr = s.as_ptr();
+        let flex = Flex::from(s);
+        assert!(matches!(flex, Flex::Give(ref b) if b.as_ptr() == ptr));
+        assert_eq!(&*flex, "hello");
+    }
+
+    #[test]
+    fn from_char() {
+        let flex: Flex<str> = 'é'.into();
+        assert_eq!(&*flex, "é");
+    }
+
+    #[test]
+    fn from_vec() {
+        let v = vec![1, 2, 3];
+        let ptr = v.as_ptr();
+        let flex = Flex::from(v);
+        assert!(matches!(flex, Flex::Give(ref b) if b.as_ptr() == ptr));
+    }
+
+    #[test]
+    fn from_array() {
+        let flex: Flex<[i32]> = [1, 2, 3].into();
+        assert!(matches!(flex, Flex::Give(ref b) if **b == [1, 2, 3]));
+    }
+
+    #[test]
+    fn into_string() {
+        let flex: Flex<str> = Flex::Lend("hello");
+        assert_eq!(String::from(flex), "hello");
+
+        let boxed: Box<str> = "world".into();
+        let ptr = boxed.as_ptr();
+        let s = String::from(Flex::Give(boxed));
+        assert_eq!(s.as_ptr(), ptr);
+        assert_eq!(s, "world");
+    }
+
+    #[test]
+    fn into_vec() {
+        let flex = Flex::Lend(&[1, 2, 3][..]);
+        assert_eq!(Vec::from(flex), [1, 2, 3]);
+
+        let boxed = vec![4, 5].into_boxed_slice();
+        let ptr = boxed.as_ptr();
+        let v = Vec::from(Flex::Give(boxed));
+        assert_eq!(v.as_ptr(), ptr);
+        assert_eq!(v, [4, 5]);
+    }
+
+    #[test]
+    fn c_string() {
+        use std::ffi::{CStr, CString};
+
+        let s = CString::new("hello").unwrap();
+        let ptr = s.as_ptr();
+        let flex = Flex::from(s);
+        assert!(matches!(flex, Flex::Give(ref b) if b.as_ptr() == ptr));
+        let owned = CString::from(flex);
+        assert_eq!(owned.as_ptr(), ptr);
+
+        let borrowed: &CStr = CStr::from_bytes_with_nul(b"hi\0").unwrap();
+        let flex = Flex::from(borrowed);
+        assert!(matches!(flex, Flex::Lend(r) if r == borrowed));
+        assert_eq!(flex.claim().to_bytes(), b"hi");
+    }
+}
+
+// FlexMut conversion tests
+mod flex_mut {
+    use super::*;
+    use flex::FlexMut;
+
+    #[test]
+    fn from_mut_ref() {
+        let mut data = [1, 2, 3];
+        let mut flex = FlexMut::from(&mut data[..]);
+        flex[1] = 5;
+        assert_eq!(data, [1, 5, 3]);
+    }
+
+    #[test]
+    fn as_mut() {
+        let mut data = vec![1, 2, 3];
+        let mut flex = FlexMut::from(&mut data);
+        let slice: &mut [i32] = flex.as_mut();
+        slice[2] = 6;
+        assert_eq!(data, [1, 2, 6]);
+    }
+
+    #[test]
+    fn into_flex_from_lend() {
+        let mut data = [1, 2, 3];
+        let flex: Flex<[i32]> = FlexMut::from(&mut data[..]).into();
+        assert!(matches!(flex, Flex::Lend(_)));
+        assert_eq!(&*flex, &[1, 2, 3]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn into_flex_from_give() {
+        let boxed = vec![1, 2, 3].into_boxed_slice();
+        let ptr = boxed.as_ptr();
+        let flex: Flex<[i32]> = FlexMut::from(boxed).into();
+        assert!(matches!(flex, Flex::Give(_)));
+        assert_eq!(flex.as_ptr(), ptr);
+    }
+}
+
+// str and [u8] conversion tests
+mod utf8 {
+    use super::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn lend_str_to_bytes() {
+        let text = "hello";
+        let bytes = Flex::<[u8]>::from(Flex::Lend(text));
+        assert!(matches!(bytes, Flex::Lend(b) if b.as_ptr() == text.as_ptr()));
+    }
+
+    #[test]
+    fn lend_bytes_to_str() {
+        let text = Flex::<str>::try_from(Flex::Lend(&b"hello"[..])).unwrap();
+        assert!(matches!(text, Flex::Lend("hello")));
+    }
+
+    #[test]
+    fn lend_invalid() {
+        let error = Flex::<str>::try_from(Flex::Lend(&[0x61, 0xff][..])).unwrap_err();
+        assert_eq!(error.as_bytes(), &[0x61, 0xff]);
+        assert_eq!(error.utf8_error().valid_up_to(), 1);
+        assert_eq!(error.to_string(), error.utf8_error().to_string());
+        assert!(matches!(error.into_flex(), Flex::Lend(..)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give_str_to_bytes() {
+        let boxed: Box<str> = "hello".into();
+        let ptr = boxed.as_ptr();
+        let bytes = Flex::<[u8]>::from(Flex::Give(boxed));
+        assert!(matches!(bytes, Flex::Give(b) if b.as_ptr() == ptr));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give_bytes_to_str() {
+        let boxed: Box<[u8]> = b"hello".to_vec().into();
+        let ptr = boxed.as_ptr();
+        let text = Flex::<str>::try_from(Flex::Give(boxed)).unwrap();
+        assert!(matches!(text, Flex::Give(b) if b.as_ptr() == ptr && &*b == "hello"));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give_invalid() {
+        let boxed: Box<[u8]> = vec![0xff].into();
+        let ptr = boxed.as_ptr();
+        let error = Flex::<str>::try_from(Flex::Give(boxed)).unwrap_err();
+        assert!(matches!(error.into_flex(), Flex::Give(b) if b.as_ptr() == ptr));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error() {
+        let error = Flex::<str>::try_from(Flex::Lend(&[0xff][..])).unwrap_err();
+        let _: &dyn std::error::Error = &error;
+    }
+}
+
+// Slice to array conversion tests
+mod array {
+    use super::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn lend() {
+        let slice = [1, 2, 3];
+        let array = Flex::<[i32; 3]>::try_from(Flex::Lend(&slice[..])).unwrap();
+        assert!(matches!(array, Flex::Lend(a) if a.as_ptr() == slice.as_ptr()));
+    }
+
+    #[test]
+    fn lend_wrong_length() {
+        let error = Flex::<[i32; 2]>::try_from(Flex::Lend(&[1, 2, 3][..])).unwrap_err();
+        assert_eq!(error.expected(), 2);
+        assert_eq!(error.as_slice(), &[1, 2, 3]);
+        assert_eq!(error.to_string(), "expected 2 elements, found 3");
+        assert!(matches!(error.into_flex(), Flex::Lend(&[1, 2, 3])));
+    }
+
+    #[test]
+    fn empty() {
+        let array = Flex::<[u8; 0]>::try_from(Flex::Lend(&[][..])).unwrap();
+        assert_eq!(*array, []);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give() {
+        let boxed: Box<[u8]> = vec![1, 2].into();
+        let ptr = boxed.as_ptr();
+        let array = Flex::<[u8; 2]>::try_from(Flex::Give(boxed)).unwrap();
+        assert!(matches!(array, Flex::Give(a) if a.as_ptr() == ptr && *a == [1, 2]));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give_wrong_length() {
+        let boxed: Box<[u8]> = vec![1, 2].into();
+        let ptr = boxed.as_ptr();
+        let error = Flex::<[u8; 3]>::try_from(Flex::Give(boxed)).unwrap_err();
+        assert!(matches!(error.into_flex(), Flex::Give(b) if b.as_ptr() == ptr));
+    }
+}
@@ -219,3 +219,28 @@ mod hash {
         assert_eq!(hash_value(&lend), hash_value(&give));
     }
 }
+
+// FlexMut forwarding tests
+mod flex_mut {
+    use flex::FlexMut;
+
+    #[test]
+    fn eq_and_ord() {
+        let mut a = [1, 2, 3];
+        let mut b = [1, 2, 4];
+        let flex1 = FlexMut::Lend(&mut a[..]);
+        let flex2 = FlexMut::Lend(&mut b[..]);
+        assert_ne!(flex1, flex2);
+        assert!(flex1 < flex2);
+        assert_eq!(flex1, &[1, 2, 3][..]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn lend_eq_give() {
+        let mut data = [1, 2, 3];
+        let lend = FlexMut::Lend(&mut data[..]);
+        let give = FlexMut::Give(vec![1, 2, 3].into_boxed_slice());
+        assert_eq!(lend, give);
+    }
+}
@@ -0,0 +1,136 @@
+use core::any::Any;
+use flex::Flex;
+
+// Borrowing downcast tests
+mod downcast_ref {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let value = 42u32;
+        let flex: Flex<dyn Any> = Flex::Lend(&value);
+        assert_eq!(flex.downcast_ref::<u32>(), Some(&42));
+        assert_eq!(flex.downcast_ref::<i32>(), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give() {
+        let flex: Flex<dyn Any + Send> = Flex::Give(Box::new(String::from("hi")));
+        assert_eq!(flex.downcast_ref::<String>().unwrap(), "hi");
+        assert!(flex.downcast_ref::<&str>().is_none());
+    }
+
+    #[test]
+    fn send_sync() {
+        let value = 'x';
+        let flex: Flex<dyn Any + Send + Sync> = Flex::Lend(&value);
+        assert_eq!(flex.downcast_ref::<char>(), Some(&'x'));
+    }
+}
+
+// Mutable downcast tests
+mod downcast_mut {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let value = 42u32;
+        let mut flex: Flex<dyn Any> = Flex::Lend(&value);
+        assert!(flex.downcast_mut::<u32>().is_none());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give() {
+        let mut flex: Flex<dyn Any + Send + Sync> = Flex::Give(Box::new(1u8));
+        *flex.downcast_mut::<u8>().unwrap() += 1;
+        assert!(flex.downcast_mut::<u16>().is_none());
+        assert_eq!(flex.downcast_ref::<u8>(), Some(&2));
+    }
+}
+
+// Consuming downcast tests
+#[cfg(feature = "alloc")]
+mod consuming {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let value = 42u32;
+        let flex: Flex<dyn Any> = Flex::Lend(&value);
+        let flex = flex.downcast::<u32>().unwrap();
+        assert!(matches!(flex, Flex::Lend(r) if core::ptr::eq(r, &value)));
+    }
+
+    #[test]
+    fn give() {
+        let boxed = Box::new(42u32);
+        let ptr: *const u32 = &*boxed;
+        let flex: Flex<dyn Any + Send> = Flex::Give(boxed);
+        match flex.downcast::<u32>().unwrap() {
+            Flex::Give(b) => assert_eq!(&*b as *const u32, ptr),
+            Flex::Lend(..) => panic!("expected Give"),
+        }
+    }
+
+    #[test]
+    fn mismatch() {
+        let value = 42u32;
+        let flex: Flex<dyn Any + Send + Sync> = Flex::Lend(&value);
+        let flex = flex.downcast::<i64>().unwrap_err();
+        assert_eq!(flex.downcast_ref::<u32>(), Some(&42));
+
+        let flex: Flex<dyn Any> = Flex::Give(Box::new(42u32));
+        let flex = flex.downcast::<i64>().unwrap_err();
+        assert!(matches!(flex, Flex::Give(..)));
+    }
+}
+
+// Error downcast tests
+mod error {
+    use super::*;
+    use core::error::Error;
+    use core::fmt;
+
+    #[derive(Debug, PartialEq)]
+    struct MyError;
+
+    impl fmt::Display for MyError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("my error")
+        }
+    }
+
+    impl Error for MyError {}
+
+    #[test]
+    fn lend_ref() {
+        let flex: Flex<dyn Error> = Flex::Lend(&MyError);
+        assert_eq!(flex.downcast_ref::<MyError>(), Some(&MyError));
+        assert_eq!(flex.downcast_ref::<fmt::Error>(), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn lend() {
+        let flex: Flex<dyn Error> = Flex::Lend(&MyError);
+        assert!(matches!(flex.downcast::<MyError>(), Ok(Flex::Lend(..))));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give() {
+        let mut flex = Flex::<dyn Error + Send + Sync>::from_error(MyError);
+        assert!(flex.downcast_mut::<MyError>().is_some());
+        assert!(matches!(flex.downcast::<MyError>(), Ok(Flex::Give(..))));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn mismatch() {
+        let flex = Flex::<dyn Error + Send>::from_error(MyError);
+        let flex = flex.downcast::<fmt::Error>().unwrap_err();
+        assert_eq!(flex.to_string(), "my error");
+    }
+}
@@ -0,0 +1,117 @@
+use core::error::Error;
+use core::fmt;
+use flex::Flex;
+
+#[derive(Debug)]
+struct Inner;
+
+impl fmt::Display for Inner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("inner")
+    }
+}
+
+impl Error for Inner {}
+
+#[derive(Debug)]
+struct Outer(Inner);
+
+impl fmt::Display for Outer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("outer")
+    }
+}
+
+impl Error for Outer {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+static OUTER: Outer = Outer(Inner);
+
+// Error trait forwarding tests
+mod forwarding {
+    use super::*;
+
+    #[test]
+    fn lend_display_and_source() {
+        let flex: Flex<dyn Error + Sync> = Flex::Lend(&OUTER);
+        assert_eq!(flex.to_string(), "outer");
+        assert_eq!(flex.source().unwrap().to_string(), "inner");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give_source() {
+        let flex = Flex::<dyn Error + Send + Sync>::from_error(Outer(Inner));
+        assert!(matches!(flex, Flex::Give(_)));
+        assert_eq!(flex.source().unwrap().to_string(), "inner");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn question_mark() {
+        fn fails() -> Result<(), Flex<'static, dyn Error + Send + Sync>> {
+            Err(Flex::Lend(&OUTER))
+        }
+
+        fn wrapper() -> Result<(), Box<dyn Error + Send + Sync>> {
+            fails()?;
+            Ok(())
+        }
+
+        assert_eq!(wrapper().unwrap_err().to_string(), "outer");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn as_source() {
+        #[derive(Debug)]
+        struct Wrapper(Flex<'static, dyn Error + Send + Sync>);
+
+        impl fmt::Display for Wrapper {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("wrapper")
+            }
+        }
+
+        impl Error for Wrapper {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let wrapper = Wrapper(Flex::<dyn Error + Send + Sync>::from_error(Inner));
+        assert_eq!(wrapper.source().unwrap().to_string(), "inner");
+    }
+}
+
+// Boxed error conversion tests
+#[cfg(feature = "alloc")]
+mod conversion {
+    use super::*;
+
+    #[test]
+    fn from_error_send() {
+        let flex = Flex::<dyn Error + Send>::from_error(Inner);
+        assert_eq!(flex.to_string(), "inner");
+    }
+
+    #[test]
+    fn lend_into_boxed_error() {
+        let flex: Flex<dyn Error> = Flex::Lend(&OUTER);
+        let boxed = flex.into_boxed_error();
+        assert_eq!(boxed.to_string(), "outer");
+        assert_eq!(boxed.source().unwrap().to_string(), "inner");
+    }
+
+    #[test]
+    fn give_into_boxed_error() {
+        let boxed: Box<dyn Error + Send + Sync> = Box::new(Inner);
+        let ptr = &*boxed as *const _ as *const u8;
+        let flex = Flex::from(boxed);
+        let boxed = flex.into_boxed_error();
+        assert_eq!(&*boxed as *const _ as *const u8, ptr);
+    }
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "alloc")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use flex::{Flex, TryCloneBox};
+
+// An allocator that fails on request, to exercise the error paths
+struct Failing;
+
+std::thread_local! {
+    static FAIL: Cell<bool> = const { Cell::new(false) };
+}
+
+unsafe impl GlobalAlloc for Failing {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        match FAIL.with(Cell::get) {
+            true => std::ptr::null_mut(),
+            false => System.alloc(layout),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Failing = Failing;
+
+// Runs `f` with every allocation on this thread failing
+fn failing<R>(f: impl FnOnce() -> R) -> R {
+    FAIL.with(|fail| fail.set(true));
+    let result = f();
+    FAIL.with(|fail| fail.set(false));
+    result
+}
+
+// TryCloneBox trait tests
+mod try_clone_box {
+    use super::*;
+
+    #[test]
+    fn sized() {
+        let boxed = 42u64.try_clone_box().unwrap();
+        assert_eq!(*boxed, 42);
+    }
+
+    #[test]
+    fn zero_sized() {
+        let boxed = ().try_clone_box().unwrap();
+        assert_eq!(*boxed, ());
+    }
+
+    #[test]
+    fn slice() {
+        let boxed = [String::from("a"), String::from("b")][..]
+            .try_clone_box()
+            .unwrap();
+        assert_eq!(&*boxed, &["a", "b"]);
+    }
+
+    #[test]
+    fn str() {
+        let boxed = "hello".try_clone_box().unwrap();
+        assert_eq!(&*boxed, "hello");
+    }
+}
+
+// Fallible Flex methods
+mod methods {
+    use super::*;
+
+    #[test]
+    fn try_into_box_from_lend() {
+        let flex = Flex::Lend(&[1, 2, 3][..]);
+        let boxed = flex.try_into_box().unwrap();
+        assert_eq!(&*boxed, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn try_into_box_from_give() {
+        let boxed = vec![1, 2, 3].into_boxed_slice();
+        let ptr = boxed.as_ptr();
+        let flex = Flex::Give(boxed);
+        let boxed = flex.try_into_box().unwrap();
+        assert_eq!(boxed.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn try_claim_sized() {
+        let value = 42;
+        let owned: Flex<'static, i32> = Flex::Lend(&value).try_claim().unwrap();
+        assert!(matches!(owned, Flex::Give(_)));
+        assert_eq!(*owned, 42);
+    }
+
+    #[test]
+    fn try_claim_str() {
+        let s = String::from("hello");
+        let owned: Flex<'static, str> = Flex::from(s.as_str()).try_claim().unwrap();
+        assert_eq!(&*owned, "hello");
+    }
+
+    #[test]
+    fn try_claim_zero_sized_slice() {
+        let zst = [(); usize::MAX];
+        let flex = Flex::Lend(&zst[..]);
+        assert!(flex.try_claim().is_ok());
+    }
+}
+
+// Allocation failure tests
+mod failure {
+    use super::*;
+
+    #[test]
+    fn try_clone_box() {
+        assert!(failing(|| 42u64.try_clone_box()).is_err());
+        assert!(failing(|| [1, 2, 3][..].try_clone_box()).is_err());
+        assert!(failing(|| "hello".try_clone_box()).is_err());
+    }
+
+    #[test]
+    fn try_into_box() {
+        let flex = Flex::Lend(&[1, 2, 3][..]);
+        assert!(failing(|| flex.try_into_box()).is_err());
+    }
+
+    #[test]
+    fn try_claim() {
+        let flex = Flex::from("hello");
+        assert!(failing(|| flex.try_claim()).is_err());
+    }
+
+    // Owned data needs no allocation, so it cannot fail
+    #[test]
+    fn give() {
+        let flex = Flex::Give(vec![1, 2, 3].into_boxed_slice());
+        let boxed = failing(|| flex.try_into_box()).unwrap();
+        assert_eq!(&*boxed, &[1, 2, 3]);
+    }
+}
@@ -84,3 +84,22 @@ mod pointer {
         assert!(formatted.starts_with("0x"));
     }
 }
+
+// FlexMut formatting tests
+mod flex_mut {
+    use flex::FlexMut;
+
+    #[test]
+    fn display() {
+        let mut value = 42;
+        let flex = FlexMut::Lend(&mut value);
+        assert_eq!(format!("{}", flex), "42");
+    }
+
+    #[test]
+    fn lower_hex() {
+        let mut value = 255;
+        let flex = FlexMut::Lend(&mut value);
+        assert_eq!(format!("{:x}", flex), "ff");
+    }
+}
@@ -0,0 +1,156 @@
+use core::convert::TryFrom;
+use flex::{ArrayBuf, ArrayStr, CapacityError, FlexBuf, FlexStr};
+
+// Inline owner tests
+mod owners {
+    use super::*;
+
+    #[test]
+    fn buf_from_slice() {
+        let buf = ArrayBuf::<u8, 4>::try_from(&[1, 2, 3][..]).unwrap();
+        assert_eq!(&*buf, &[1, 2, 3]);
+        assert_eq!(buf.capacity(), 4);
+    }
+
+    #[test]
+    fn buf_too_long() {
+        let err = ArrayBuf::<u8, 2>::try_from(&[1, 2, 3][..]).unwrap_err();
+        assert_eq!(
+            err,
+            CapacityError {
+                capacity: 2,
+                len: 3
+            }
+        );
+    }
+
+    #[test]
+    fn buf_deref_mut() {
+        let mut buf = ArrayBuf::<u8, 4>::try_from(&[1, 2][..]).unwrap();
+        buf[0] = 3;
+        assert_eq!(&*buf, &[3, 2]);
+    }
+
+    #[test]
+    fn str_from_str() {
+        let s = ArrayStr::<8>::try_from("hello").unwrap();
+        assert_eq!(&*s, "hello");
+    }
+
+    #[test]
+    fn str_too_long() {
+        assert!(ArrayStr::<4>::try_from("hello").is_err());
+    }
+
+    #[test]
+    fn error_display() {
+        let err = CapacityError {
+            capacity: 2,
+            len: 3,
+        };
+        assert_eq!(format!("{}", err), "length 3 exceeds inline capacity 2");
+    }
+}
+
+// Inline claim tests
+mod claim {
+    use super::*;
+
+    #[test]
+    fn buf_try_claim() {
+        let data = [1, 2, 3];
+        let owned: FlexBuf<'static, u8, 4> = FlexBuf::Lend(&data[..]).try_claim().unwrap();
+        assert!(matches!(owned, FlexBuf::Give(_)));
+        assert_eq!(&*owned, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn buf_try_claim_too_long() {
+        let data = [1, 2, 3];
+        let flex: FlexBuf<u8, 2> = FlexBuf::Lend(&data[..]);
+        assert!(flex.try_claim().is_err());
+    }
+
+    #[test]
+    fn str_try_claim() {
+        let s = String::from("hello");
+        let owned: FlexStr<'static, 8> = FlexStr::Lend(s.as_str()).try_claim().unwrap();
+        drop(s);
+        assert_eq!(&*owned, "hello");
+    }
+
+    #[test]
+    fn str_lend_eq_give() {
+        let lend: FlexStr<8> = FlexStr::Lend("hello");
+        let give: FlexStr<8> = FlexStr::Lend("hello").try_claim().unwrap();
+        assert_eq!(lend, give);
+        assert_eq!(format!("{}", give), "hello");
+    }
+
+    #[test]
+    fn str_to_mut() {
+        let mut flex: FlexStr<8> = FlexStr::Lend("hello").try_claim().unwrap();
+        flex.get_mut().unwrap().make_ascii_uppercase();
+        assert_eq!(&*flex, "HELLO");
+    }
+}
+
+// By-value owner tests
+mod own {
+    use flex::{FlexOwn, Own};
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Config {
+        retries: u32,
+        name: &'static str,
+    }
+
+    fn hash_value<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn claim_clones_inline() {
+        let config = Config {
+            retries: 3,
+            name: "default",
+        };
+        let owned: FlexOwn<'static, Config> = FlexOwn::Lend(&config).claim();
+        assert!(matches!(owned, FlexOwn::Give(Own(_))));
+        assert_eq!(*owned, config);
+    }
+
+    #[test]
+    fn from_value() {
+        let flex = FlexOwn::from(42u64);
+        assert_eq!(flex, 42);
+        assert_eq!(format!("{:x}", flex), "2a");
+    }
+
+    #[test]
+    fn lend_eq_give() {
+        let value = 42u64;
+        let lend = FlexOwn::Lend(&value);
+        let give = FlexOwn::from(42u64);
+        assert_eq!(lend, give);
+        assert_eq!(hash_value(&lend), hash_value(&give));
+    }
+
+    #[test]
+    fn to_mut() {
+        let value = 1u64;
+        let mut flex = FlexOwn::Lend(&value);
+        *flex.to_mut() += 1;
+        assert_eq!(flex, 2);
+        assert_eq!(value, 1);
+    }
+
+    #[test]
+    fn size_of_sized() {
+        assert!(std::mem::size_of::<FlexOwn<u64>>() <= 2 * std::mem::size_of::<u64>());
+    }
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "alloc")]
+
+use flex::Flex;
+
+// FromIterator trait tests
+mod from_iterator {
+    use super::*;
+
+    #[test]
+    fn slice() {
+        let flex: Flex<[i32]> = (1..4).collect();
+        assert!(matches!(flex, Flex::Give(_)));
+        assert_eq!(&*flex, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn str_from_chars() {
+        let flex: Flex<str> = "hello".chars().rev().collect();
+        assert_eq!(&*flex, "olleh");
+    }
+
+    #[test]
+    fn str_from_strs() {
+        let flex: Flex<str> = vec!["hello", " ", "world"].into_iter().collect();
+        assert_eq!(&*flex, "hello world");
+    }
+}
+
+// Extend trait tests
+mod extend {
+    use super::*;
+
+    #[test]
+    fn lend_slice() {
+        let data = [1, 2];
+        let mut flex = Flex::Lend(&data[..]);
+        flex.extend(vec![3, 4]);
+        assert!(matches!(flex, Flex::Give(_)));
+        assert_eq!(&*flex, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn give_slice() {
+        let mut flex = Flex::Give(vec![1, 2].into_boxed_slice());
+        flex.extend(3..5);
+        assert_eq!(&*flex, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn lend_str() {
+        let mut flex = Flex::Lend("hello");
+        flex.extend(vec![", ", "world"]);
+        assert_eq!(&*flex, "hello, world");
+    }
+
+    #[test]
+    fn give_str_chars() {
+        let mut flex = Flex::Give(String::from("ab").into_boxed_str());
+        flex.extend("cd".chars());
+        assert_eq!(&*flex, "abcd");
+    }
+}
+
+// By-value IntoIterator tests
+mod into_iter {
+    use super::*;
+
+    #[test]
+    fn lend_clones() {
+        let data = [String::from("a"), String::from("b")];
+        let flex = Flex::Lend(&data[..]);
+        let collected: Vec<String> = flex.into_iter().collect();
+        assert_eq!(collected, data);
+    }
+
+    #[test]
+    fn give_moves() {
+        let flex = Flex::Give(vec![String::from("a"), String::from("b")].into_boxed_slice());
+        let mut iter = flex.into_iter();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back().as_deref(), Some("b"));
+        assert_eq!(iter.next().as_deref(), Some("a"));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn for_loop() {
+        let flex: Flex<[i32]> = (1..4).collect();
+        let mut sum = 0;
+        for value in flex {
+            sum += value;
+        }
+        assert_eq!(sum, 6);
+    }
+
+    // Iterating a reference still borrows the elements
+    #[test]
+    fn by_reference() {
+        let data = [String::from("a"), String::from("b")];
+        let flex = Flex::Lend(&data[..]);
+
+        let first: Option<&String> = (&flex).into_iter().next();
+        assert!(std::ptr::eq(first.unwrap(), &data[0]));
+
+        let mut count = 0;
+        for value in &flex {
+            let value: &String = value;
+            assert!(data.iter().any(|d| std::ptr::eq(d, value)));
+            count += 1;
+        }
+        assert_eq!(count, 2);
+        assert_eq!(flex.len(), 2);
+    }
+}
@@ -17,6 +17,19 @@ mod construction {
         assert_eq!(&*flex, &[1, 2, 3]);
     }
 
+    // Without alloc, `Give` is uninhabited, so `Lend` alone is exhaustive
+    #[cfg(not(feature = "alloc"))]
+    #[test]
+    fn lend_exhaustive() {
+        fn len(flex: Flex<str>) -> usize {
+            match flex {
+                Flex::Lend(r) => r.len(),
+            }
+        }
+
+        assert_eq!(len(Flex::Lend("hello")), 5);
+    }
+
     #[cfg(feature = "alloc")]
     #[test]
     fn give_from_box() {
@@ -169,4 +182,152 @@ mod alloc_methods {
         let owned: Flex<'static, str> = borrowed.claim();
         assert_eq!(&*owned, "hello");
     }
+
+    #[test]
+    fn get_mut_lend() {
+        let mut flex = Flex::Lend(&[1, 2, 3][..]);
+        assert!(flex.get_mut().is_none());
+    }
+
+    #[test]
+    fn get_mut_give() {
+        let mut flex = Flex::Give(vec![1, 2, 3].into_boxed_slice());
+        flex.get_mut().unwrap()[1] = 5;
+        assert_eq!(&*flex, &[1, 5, 3]);
+    }
+
+    #[test]
+    fn to_mut_lend_claims() {
+        let data = [1, 2, 3];
+        let mut flex = Flex::Lend(&data[..]);
+        flex.to_mut()[0] = 4;
+        assert!(matches!(flex, Flex::Give(..)));
+        assert_eq!(&*flex, &[4, 2, 3]);
+        assert_eq!(data, [1, 2, 3]);
+    }
+
+    #[test]
+    fn to_mut_give_in_place() {
+        let mut flex = Flex::Give(String::from("hello").into_boxed_str());
+        let ptr = flex.as_ptr();
+        flex.to_mut().make_ascii_uppercase();
+        assert_eq!(flex.as_ptr(), ptr);
+        assert_eq!(&*flex, "HELLO");
+    }
+}
+
+// FlexMut construction and mutation tests
+mod flex_mut {
+    use flex::FlexMut;
+
+    #[test]
+    fn lend_deref_mut() {
+        let mut data = [1, 2, 3];
+        let mut flex = FlexMut::Lend(&mut data[..]);
+        flex[0] = 4;
+        assert_eq!(&*flex, &[4, 2, 3]);
+        assert_eq!(data, [4, 2, 3]);
+    }
+
+    #[test]
+    fn lend_iter_mut() {
+        let mut data = [1, 2, 3];
+        let mut flex = FlexMut::Lend(&mut data[..]);
+        for value in &mut flex {
+            *value *= 2;
+        }
+        assert_eq!(data, [2, 4, 6]);
+    }
+
+    #[test]
+    fn default_slice() {
+        let flex: FlexMut<[i32]> = FlexMut::default();
+        assert_eq!(&*flex, &[]);
+    }
+
+    #[test]
+    fn lend_trait_object() {
+        trait Counter {
+            fn bump(&mut self);
+        }
+
+        impl Counter for i32 {
+            fn bump(&mut self) {
+                *self += 1;
+            }
+        }
+
+        let mut value = 41;
+        let mut flex: FlexMut<dyn Counter> = FlexMut::from(&mut value as &mut dyn Counter);
+        flex.bump();
+        assert_eq!(value, 42);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give_deref_mut() {
+        let mut flex = FlexMut::Give(String::from("hello").into_boxed_str());
+        flex.make_ascii_uppercase();
+        assert_eq!(&*flex, "HELLO");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn claim_lend_to_static() {
+        let mut data = [1, 2, 3];
+        let mut owned: FlexMut<'static, [i32]> = FlexMut::Lend(&mut data[..]).claim();
+        owned[0] = 4;
+        assert_eq!(&*owned, &[4, 2, 3]);
+        assert_eq!(data, [1, 2, 3]);
+    }
+}
+
+// FlexWith custom owner tests
+mod owner {
+    use flex::FlexWith;
+    use std::ops::Deref;
+
+    #[derive(Clone, Debug)]
+    struct Handle(&'static str);
+
+    impl Deref for Handle {
+        type Target = str;
+
+        fn deref(&self) -> &str {
+            self.0
+        }
+    }
+
+    impl From<&str> for Handle {
+        fn from(_: &str) -> Self {
+            Handle("claimed")
+        }
+    }
+
+    #[test]
+    fn give_deref() {
+        let flex: FlexWith<str, Handle> = FlexWith::Give(Handle("hello"));
+        assert_eq!(&*flex, "hello");
+        assert_eq!(flex.len(), 5);
+    }
+
+    #[test]
+    fn lend_eq_give() {
+        let lend: FlexWith<str, Handle> = FlexWith::Lend("hello");
+        let give: FlexWith<str, Handle> = FlexWith::Give(Handle("hello"));
+        assert_eq!(lend, give);
+    }
+
+    #[test]
+    fn claim_uses_owner() {
+        let lend: FlexWith<str, Handle> = FlexWith::Lend("hello");
+        let owned: FlexWith<'static, str, Handle> = lend.claim();
+        assert_eq!(&*owned, "claimed");
+    }
+
+    #[test]
+    fn clone_uses_owner() {
+        let give: FlexWith<str, Handle> = FlexWith::Give(Handle("hello"));
+        assert!(matches!(give.clone(), FlexWith::Give(Handle("hello"))));
+    }
 }
@@ -0,0 +1,75 @@
+//! Model checks for `AtomicFlex`.
+//!
+//! Run with `RUSTFLAGS="--cfg loom" cargo test --release --features alloc --test loom`.
+
+#![cfg(all(loom, feature = "alloc"))]
+
+use std::sync::Arc;
+
+use flex::{AtomicFlex, FlexArc};
+use loom::thread;
+
+static DEFAULT: usize = 0;
+
+// A load racing a store sees either value and never a reclaimed one
+#[test]
+fn load_store() {
+    loom::model(|| {
+        let cell = loom::sync::Arc::new(AtomicFlex::from(&DEFAULT));
+
+        let reader = {
+            let cell = cell.clone();
+            thread::spawn(move || *cell.load())
+        };
+
+        cell.store(FlexArc::Give(Arc::new(1)));
+        assert!(reader.join().unwrap() <= 1);
+        assert_eq!(*cell.load(), 1);
+    });
+}
+
+// Two loads racing a swap, with the second load after the swap
+#[test]
+fn loads_swap() {
+    loom::model(|| {
+        let cell = loom::sync::Arc::new(AtomicFlex::from(Arc::new(1usize)));
+
+        let reader = {
+            let cell = cell.clone();
+            thread::spawn(move || {
+                let first = *cell.load();
+                let second = *cell.load();
+                assert!(first <= second);
+            })
+        };
+
+        let old = cell.swap(FlexArc::Give(Arc::new(2)));
+        assert_eq!(*old, 1);
+        reader.join().unwrap();
+    });
+}
+
+// Two stores racing each other and a load
+#[test]
+fn stores() {
+    loom::model(|| {
+        let cell = loom::sync::Arc::new(AtomicFlex::from(&DEFAULT));
+
+        let writer = {
+            let cell = cell.clone();
+            thread::spawn(move || cell.store(FlexArc::Give(Arc::new(1))))
+        };
+
+        let reader = {
+            let cell = cell.clone();
+            thread::spawn(move || *cell.load())
+        };
+
+        cell.store(FlexArc::Give(Arc::new(2)));
+        writer.join().unwrap();
+        assert!(reader.join().unwrap() <= 2);
+
+        let last = *cell.load();
+        assert!(last == 1 || last == 2);
+    });
+}
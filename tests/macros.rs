@@ -0,0 +1,165 @@
+#![cfg(feature = "macros")]
+
+extern crate flex as renamed;
+
+use core::fmt::Debug;
+use flex::Flex;
+
+#[flex::claimable]
+trait Shape {
+    fn area(&self) -> u32;
+}
+
+#[flex::claimable]
+trait Named: Debug {
+    fn name(&self) -> String;
+}
+
+#[flex::claimable]
+trait Convert<T> {
+    fn convert(&self) -> T;
+}
+
+#[derive(Clone, Debug)]
+struct Square(u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+impl Named for Square {
+    fn name(&self) -> String {
+        format!("square {}", self.0)
+    }
+}
+
+impl Convert<u64> for Square {
+    fn convert(&self) -> u64 {
+        self.0.into()
+    }
+}
+
+// Claiming through the attribute
+mod claim {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let square = Square(3);
+        let borrowed: Flex<dyn Shape> = Flex::Lend(&square);
+        let owned: Flex<'static, dyn Shape> = borrowed.claim();
+        assert!(matches!(owned, Flex::Give(..)));
+        assert_eq!(owned.area(), 9);
+    }
+
+    #[test]
+    fn send_sync() {
+        let square = Square(2);
+        let borrowed: Flex<dyn Shape + Send + Sync> = Flex::Lend(&square);
+        let owned: Flex<'static, dyn Shape + Send + Sync> = borrowed.claim();
+        let area = std::thread::spawn(move || owned.area());
+        assert_eq!(area.join().unwrap(), 4);
+    }
+
+    #[test]
+    fn supertrait() {
+        let square = Square(1);
+        let borrowed: Flex<dyn Named> = Flex::Lend(&square);
+        let owned: Flex<'static, dyn Named> = borrowed.claim();
+        assert_eq!(owned.name(), "square 1");
+        assert_eq!(format!("{:?}", owned), "Give(Square(1))");
+    }
+
+    #[test]
+    fn generic() {
+        let square = Square(7);
+        let borrowed: Flex<dyn Convert<u64>> = Flex::Lend(&square);
+        let owned: Flex<'static, dyn Convert<u64>> = borrowed.claim();
+        assert_eq!(owned.convert(), 7);
+    }
+
+    #[test]
+    fn non_static() {
+        #[derive(Clone)]
+        struct Borrowed<'a>(&'a u32);
+
+        impl Shape for Borrowed<'_> {
+            fn area(&self) -> u32 {
+                *self.0
+            }
+        }
+
+        let value = 5;
+        let inner = Borrowed(&value);
+        let borrowed: Flex<dyn Shape + '_> = Flex::Lend(&inner);
+        let owned: Box<dyn Shape + '_> = borrowed.into_box();
+        assert_eq!(owned.area(), 5);
+    }
+}
+
+// Cloning through the attribute
+mod clone {
+    use super::*;
+
+    #[test]
+    fn give() {
+        let owned: Flex<dyn Shape + Send> = Flex::Give(Box::new(Square(4)));
+        let cloned = owned.clone();
+        assert_eq!(cloned.area(), 16);
+        assert!(!core::ptr::eq(
+            &*owned as *const _ as *const u8,
+            &*cloned as *const _ as *const u8
+        ));
+    }
+
+    #[test]
+    fn boxed() {
+        let boxed: Box<dyn Shape + Sync> = Box::new(Square(2));
+        assert_eq!(boxed.clone().area(), 4);
+    }
+}
+
+// Naming a renamed crate
+mod renamed_crate {
+    use renamed::{Flex, IntoOwned, Reborrow};
+
+    #[renamed::claimable(crate = "renamed")]
+    trait Measure {
+        fn size(&self) -> usize;
+    }
+
+    #[derive(Clone)]
+    struct Pair;
+
+    impl Measure for Pair {
+        fn size(&self) -> usize {
+            2
+        }
+    }
+
+    #[derive(renamed::IntoOwned, renamed::Reborrow)]
+    #[flex(crate = "renamed")]
+    struct Message<'a> {
+        name: Flex<'a, str>,
+    }
+
+    #[test]
+    fn claimable() {
+        let borrowed: Flex<dyn Measure> = Flex::Lend(&Pair);
+        let owned: Flex<'static, dyn Measure> = borrowed.claim();
+        assert_eq!(owned.size(), 2);
+    }
+
+    #[test]
+    fn derives() {
+        let text = String::from("hello");
+        let message = Message {
+            name: Flex::Lend(&text),
+        };
+        let owned: Message<'static> = message.into_owned();
+        let view = owned.reborrow();
+        assert!(matches!(view.name, Flex::Lend("hello")));
+    }
+}
@@ -0,0 +1,116 @@
+use core::str::Utf8Error;
+
+use flex::{Flex, FlexWith, Unowned};
+
+struct Header {
+    name: &'static str,
+    size: u64,
+}
+
+// map tests
+mod map {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let text = "hello";
+        let flex: FlexWith<str, Unowned<str>> = FlexWith::Lend(text);
+        let bytes: FlexWith<[u8], Unowned<[u8]>> = flex.map(str::as_bytes, |o| o.never());
+        assert!(matches!(bytes, FlexWith::Lend(r) if r.as_ptr() == text.as_ptr()));
+    }
+
+    #[test]
+    fn field() {
+        let header = Header {
+            name: "data",
+            size: 4,
+        };
+        let flex: FlexWith<Header, Unowned<Header>> = FlexWith::Lend(&header);
+        let size: FlexWith<u64, Unowned<u64>> = flex.map(|h| &h.size, |o| o.never());
+        assert_eq!(*size, 4);
+        assert_eq!(header.name, "data");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give() {
+        let boxed: Box<str> = "hello".into();
+        let ptr = boxed.as_ptr();
+        let bytes: Flex<[u8]> = Flex::Give(boxed).map(str::as_bytes, str::into_boxed_bytes);
+        assert!(matches!(bytes, Flex::Give(b) if b.as_ptr() == ptr));
+    }
+}
+
+// try_map tests
+mod try_map {
+    use super::*;
+
+    fn lend_str(bytes: &[u8]) -> Result<Flex<'_, str>, Utf8Error> {
+        Flex::Lend(bytes).try_map(core::str::from_utf8, |_| unreachable!())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_str(bytes: Flex<[u8]>) -> Result<Flex<str>, Utf8Error> {
+        bytes.try_map(core::str::from_utf8, |b| {
+            String::from_utf8(b.into_vec())
+                .map(String::into_boxed_str)
+                .map_err(|e| e.utf8_error())
+        })
+    }
+
+    #[test]
+    fn lend() {
+        let text = lend_str(b"hello").unwrap();
+        assert!(matches!(text, FlexWith::Lend("hello")));
+        assert!(lend_str(&[0xff]).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give() {
+        let bytes = b"hello".to_vec().into_boxed_slice();
+        let ptr = bytes.as_ptr();
+        let text = to_str(Flex::Give(bytes)).unwrap();
+        assert!(matches!(text, Flex::Give(b) if b.as_ptr() == ptr));
+
+        let invalid = vec![0xff].into_boxed_slice();
+        assert!(to_str(Flex::Give(invalid)).is_err());
+    }
+}
+
+// map_or_claim tests
+#[cfg(feature = "alloc")]
+mod map_or_claim {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let header = Header {
+            name: "data",
+            size: 4,
+        };
+        let name: Flex<str> = Flex::Lend(&header).map_or_claim(|h| h.name);
+        assert!(matches!(name, FlexWith::Lend("data")));
+        assert_eq!(header.size, 4);
+    }
+
+    #[test]
+    fn give() {
+        let header = Flex::Give(Box::new(Header {
+            name: "data",
+            size: 4,
+        }));
+        let name: Flex<str> = header.map_or_claim(|h| h.name);
+        assert!(matches!(name, Flex::Give(b) if &*b == "data"));
+    }
+
+    #[test]
+    fn rc() {
+        use flex::FlexRc;
+        use std::rc::Rc;
+
+        let shared: FlexRc<[u8]> = FlexRc::Give(Rc::from(&[1, 2, 3][..]));
+        let tail: FlexRc<[u8]> = shared.map_or_claim(|s| &s[1..]);
+        assert_eq!(&*tail, &[2, 3]);
+    }
+}
@@ -137,3 +137,69 @@ mod usage_patterns {
         assert_eq!(&*owned, &[1, 2, 3]);
     }
 }
+
+// Tests for trait object upcasting, which requires Rust 1.86
+#[cfg(feature = "alloc")]
+#[rustversion::since(1.86)]
+mod upcasting {
+    use super::*;
+    use flex::{flex_upcast, FlexWith};
+    use std::any::Any;
+
+    trait Plugin: Any + Debug {
+        fn name(&self) -> String;
+    }
+
+    #[derive(Debug)]
+    struct Echo(u32);
+
+    impl Plugin for Echo {
+        fn name(&self) -> String {
+            format!("echo {}", self.0)
+        }
+    }
+
+    #[test]
+    fn lend_to_debug() {
+        let echo = Echo(1);
+        let plugin: Flex<dyn Plugin> = Flex::Lend(&echo);
+        assert_eq!(plugin.name(), "echo 1");
+
+        let debug = flex_upcast!(plugin => dyn Debug);
+        assert!(matches!(debug, FlexWith::Lend(..)));
+        assert_eq!(format!("{:?}", &*debug), "Echo(1)");
+    }
+
+    #[test]
+    fn lend_to_any() {
+        let echo = Echo(2);
+        let plugin: Flex<dyn Plugin> = Flex::Lend(&echo);
+        let any = flex_upcast!(plugin => dyn Any);
+        let downcast = any.downcast_ref::<Echo>().unwrap();
+        assert!(std::ptr::eq(downcast, &echo));
+    }
+
+    #[test]
+    fn give_to_any() {
+        let boxed: Box<dyn Plugin> = Box::new(Echo(3));
+        let ptr = &*boxed as *const dyn Plugin as *const u8;
+        let plugin: Flex<dyn Plugin> = Flex::Give(boxed);
+
+        let any: Flex<dyn Any> = flex_upcast!(plugin);
+        assert_eq!(&*any as *const dyn Any as *const u8, ptr);
+
+        let echo = any.downcast::<Echo>().unwrap();
+        assert!(matches!(echo, FlexWith::Give(..)));
+        assert_eq!(echo.name(), "echo 3");
+    }
+
+    #[test]
+    fn give_to_debug() {
+        fn show(flex: Flex<dyn Debug>) -> String {
+            format!("{:?}", flex)
+        }
+
+        let plugin: Flex<dyn Plugin> = Flex::Give(Box::new(Echo(4)));
+        assert_eq!(show(flex_upcast!(plugin)), "Give(Echo(4))");
+    }
+}
@@ -0,0 +1,232 @@
+use flex::IntoOwned;
+
+// IntoOwned implementations for Flex and leaf types
+mod flex_types {
+    use super::*;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn lend() {
+        use flex::Flex;
+
+        let text = String::from("hello");
+        let owned: Flex<'static, str> = Flex::from(text.as_str()).into_owned();
+        drop(text);
+        assert!(matches!(owned, Flex::Give(..)));
+        assert_eq!(&*owned, "hello");
+    }
+
+    #[test]
+    fn inline() {
+        use flex::FlexOwn;
+
+        let value = 42u64;
+        let owned: FlexOwn<'static, u64> = FlexOwn::Lend(&value).into_owned();
+        assert_eq!(*owned, 42);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn flex_mut() {
+        use flex::FlexMut;
+
+        let mut data = [1, 2, 3];
+        let owned: FlexMut<'static, [i32]> = FlexMut::Lend(&mut data[..]).into_owned();
+        assert_eq!(&*owned, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn unchanged() {
+        assert_eq!(42u8.into_owned(), 42);
+        assert_eq!('x'.into_owned(), 'x');
+        assert_eq!(().into_owned(), ());
+    }
+}
+
+// IntoOwned implementations for containers
+#[cfg(feature = "alloc")]
+mod containers {
+    use super::*;
+    use flex::Flex;
+    use std::borrow::Cow;
+    use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+    fn lend(s: &str) -> Flex<'_, str> {
+        Flex::Lend(s)
+    }
+
+    #[test]
+    fn option_and_result() {
+        let text = String::from("a");
+        let some: Option<Flex<'static, str>> = Some(lend(&text)).into_owned();
+        let ok: Result<Flex<'static, str>, u8> = Ok::<_, u8>(lend(&text)).into_owned();
+        drop(text);
+        assert_eq!(some.as_deref(), Some("a"));
+        assert_eq!(ok.as_deref(), Ok("a"));
+    }
+
+    #[test]
+    fn tuple_and_array() {
+        let text = String::from("b");
+        let tuple: (u8, Flex<'static, str>) = (1u8, lend(&text)).into_owned();
+        let array: [Flex<'static, str>; 2] = [lend(&text), lend(&text)].into_owned();
+        drop(text);
+        assert_eq!(&*tuple.1, "b");
+        assert_eq!(&*array[1], "b");
+    }
+
+    #[test]
+    fn sequences() {
+        let text = String::from("c");
+        let boxed: Box<Flex<'static, str>> = Box::new(lend(&text)).into_owned();
+        let vec: Vec<Flex<'static, str>> = vec![lend(&text)].into_owned();
+        let deque: VecDeque<Flex<'static, str>> = VecDeque::from(vec![lend(&text)]).into_owned();
+        drop(text);
+        assert_eq!(&**boxed, "c");
+        assert_eq!(&*vec[0], "c");
+        assert_eq!(&*deque[0], "c");
+    }
+
+    #[test]
+    fn maps_and_sets() {
+        let text = String::from("d");
+        let mut map = BTreeMap::new();
+        map.insert(lend(&text), vec![lend(&text)]);
+        let map: BTreeMap<Flex<'static, str>, Vec<Flex<'static, str>>> = map.into_owned();
+
+        let set: BTreeSet<_> = std::iter::once(lend(&text)).collect();
+        let set: BTreeSet<Flex<'static, str>> = set.into_owned();
+        drop(text);
+
+        assert_eq!(&*map["d"][0], "d");
+        assert!(set.contains("d"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_maps_and_sets() {
+        use std::collections::{HashMap, HashSet};
+
+        let text = String::from("e");
+        let map: HashMap<_, _> = std::iter::once((lend(&text), 1u8)).collect();
+        let map: HashMap<Flex<'static, str>, u8> = map.into_owned();
+
+        let set: HashSet<_> = std::iter::once(lend(&text)).collect();
+        let set: HashSet<Flex<'static, str>> = set.into_owned();
+        drop(text);
+
+        assert_eq!(map["e"], 1);
+        assert!(set.contains("e"));
+    }
+
+    #[test]
+    fn cow() {
+        let text = String::from("f");
+        let cow: Cow<'static, str> = IntoOwned::into_owned(Cow::Borrowed(text.as_str()));
+        drop(text);
+        assert!(matches!(cow, Cow::Owned(..)));
+    }
+}
+
+// #[derive(IntoOwned)] tests
+#[cfg(feature = "macros")]
+mod derive {
+    use super::*;
+    use flex::Flex;
+
+    #[derive(IntoOwned, Debug, PartialEq)]
+    struct Message<'a> {
+        id: u32,
+        name: Flex<'a, str>,
+        payload: Option<Flex<'a, [u8]>>,
+        tags: Vec<Flex<'a, str>>,
+    }
+
+    #[derive(IntoOwned, Debug, PartialEq)]
+    struct Pair<'a, T>(Flex<'a, str>, T);
+
+    #[derive(IntoOwned, Debug, PartialEq)]
+    struct Unit;
+
+    #[derive(IntoOwned, flex::Reborrow, Debug, PartialEq)]
+    struct Labeled<'a> {
+        name: Flex<'a, str>,
+        #[flex(clone)]
+        label: String,
+    }
+
+    #[derive(IntoOwned, Debug, PartialEq)]
+    enum Token<'a, 'b> {
+        Word(Flex<'a, str>),
+        Bytes { data: Flex<'b, [u8]>, len: usize },
+        End,
+    }
+
+    #[test]
+    fn named_struct() {
+        let text = String::from("hello");
+        let message = Message {
+            id: 7,
+            name: Flex::from(text.as_str()),
+            payload: Some(Flex::from(text.as_bytes())),
+            tags: vec![Flex::from(&text[1..])],
+        };
+
+        let owned: Message<'static> = message.into_owned();
+        drop(text);
+
+        assert_eq!(owned.id, 7);
+        assert_eq!(&*owned.name, "hello");
+        assert_eq!(owned.payload.as_deref(), Some(&b"hello"[..]));
+        assert_eq!(&*owned.tags[0], "ello");
+    }
+
+    #[test]
+    fn tuple_struct_with_generic() {
+        let text = String::from("x");
+        let pair: Pair<'static, Vec<i32>> = Pair(Flex::from(text.as_str()), vec![1]).into_owned();
+        drop(text);
+        assert_eq!(pair, Pair(Flex::Lend("x"), vec![1]));
+    }
+
+    #[test]
+    fn unit_struct() {
+        assert_eq!(Unit.into_owned(), Unit);
+    }
+
+    #[test]
+    fn with_reborrow() {
+        let text = String::from("name");
+        let labeled = Labeled {
+            name: Flex::from(text.as_str()),
+            label: String::from("label"),
+        };
+
+        let owned: Labeled<'static> = labeled.into_owned();
+        drop(text);
+        assert_eq!(&*owned.name, "name");
+        assert_eq!(owned.label, "label");
+    }
+
+    #[test]
+    fn enums() {
+        let text = String::from("word");
+        let word: Token<'static, 'static> = Token::Word(Flex::from(text.as_str())).into_owned();
+        let bytes: Token<'static, 'static> = Token::Bytes {
+            data: Flex::from(text.as_bytes()),
+            len: 4,
+        }
+        .into_owned();
+        drop(text);
+
+        assert_eq!(word, Token::Word(Flex::Lend("word")));
+        assert_eq!(
+            bytes,
+            Token::Bytes {
+                data: Flex::Lend(b"word"),
+                len: 4
+            }
+        );
+        assert_eq!(Token::End.into_owned(), Token::End);
+    }
+}
@@ -0,0 +1,113 @@
+use flex::{Flex, FlexProjected, FlexWith};
+
+// Projecting from either variant
+mod project {
+    use super::*;
+
+    #[test]
+    fn lend() {
+        let text = String::from("name=flex");
+        let pair: FlexProjected<str, (&'static str, &'static str)> =
+            FlexProjected::project(Flex::Lend(text.as_str()), |s| s.split_once('=').unwrap());
+        assert_eq!(*pair.get(), ("name", "flex"));
+        assert_eq!(pair.get().0.as_ptr(), text.as_ptr());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give() {
+        let boxed: Box<str> = "name=flex".into();
+        let ptr = boxed.as_ptr();
+        let pair: FlexProjected<str, (&'static str, &'static str)> =
+            FlexProjected::project(Flex::Give(boxed), |s| s.split_once('=').unwrap());
+
+        // Moving the projection does not move the cart's data
+        let moved = [pair];
+        assert_eq!(*moved[0].get(), ("name", "flex"));
+        assert_eq!(moved[0].get().0.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn option() {
+        let words: FlexProjected<str, Option<&'static str>> =
+            FlexProjected::project(Flex::Lend("a b"), |s| s.split(' ').nth(1));
+        assert_eq!(*words.get(), Some("b"));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn flex_view() {
+        let view: FlexProjected<[u8], Flex<'static, str>> =
+            FlexProjected::project(Flex::Give(b"hello".to_vec().into()), |b| {
+                Flex::Lend(core::str::from_utf8(b).unwrap())
+            });
+        assert!(matches!(view.get(), FlexWith::Lend("hello")));
+    }
+}
+
+// Changing the view
+mod map_project {
+    use super::*;
+
+    #[test]
+    fn narrow() {
+        let pair: FlexProjected<str, (&'static str, &'static str)> =
+            FlexProjected::project(Flex::Lend("name=flex"), |s| s.split_once('=').unwrap());
+        let value: FlexProjected<str, &'static str> = pair.map_project(|(_, v), _| v);
+        assert_eq!(*value.get(), "flex");
+        assert_eq!(value.cart(), "name=flex");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn keeps_cart() {
+        use core::marker::PhantomData;
+
+        let boxed: Box<str> = "key=value".into();
+        let ptr = boxed.as_ptr();
+        let pair: FlexProjected<str, (&'static str, &'static str)> =
+            FlexProjected::project(Flex::Give(boxed), |s| s.split_once('=').unwrap());
+        let key: FlexProjected<str, &'static str> =
+            pair.map_project(|(k, _), _: PhantomData<&()>| k);
+        assert_eq!(key.get().as_ptr(), ptr);
+
+        let cart = key.into_cart();
+        assert!(matches!(cart, Flex::Give(b) if b.as_ptr() == ptr));
+    }
+}
+
+// Other methods
+mod other {
+    use super::*;
+
+    #[test]
+    fn into_cart() {
+        let view: FlexProjected<str, &'static str> =
+            FlexProjected::project(Flex::Lend("hello"), |s| &s[1..]);
+        assert!(matches!(view.into_cart(), FlexWith::Lend("hello")));
+    }
+
+    #[test]
+    fn debug() {
+        let view: FlexProjected<str, &'static str> =
+            FlexProjected::project(Flex::Lend("hello"), |s| &s[..2]);
+        assert_eq!(
+            format!("{:?}", view),
+            "FlexProjected { cart: \"hello\", view: \"he\" }"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn drops() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let items: Box<[Rc<()>]> = vec![rc.clone(), rc.clone()].into();
+        let view: FlexProjected<[Rc<()>], &'static Rc<()>> =
+            FlexProjected::project(Flex::Give(items), |s| &s[0]);
+        assert_eq!(Rc::strong_count(view.get()), 3);
+        drop(view);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+}
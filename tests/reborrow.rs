@@ -0,0 +1,159 @@
+use flex::Reborrow;
+
+// Reborrowing Flex itself
+mod flex_types {
+    use super::*;
+    use flex::Flex;
+
+    #[test]
+    fn lend() {
+        let text = "hello";
+        let flex = Flex::Lend(text);
+        let view = flex.reborrow();
+        assert!(matches!(view, Flex::Lend(r) if core::ptr::eq(r, text)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give() {
+        let flex: Flex<'static, [u8]> = Flex::Give(vec![1, 2, 3].into_boxed_slice());
+        let view: Flex<'_, [u8]> = flex.reborrow();
+        assert!(matches!(view, Flex::Lend(r) if r.as_ptr() == flex.as_ptr()));
+    }
+
+    #[test]
+    fn trait_method() {
+        let flex = Flex::Lend(&[1, 2][..]);
+        let view = Reborrow::reborrow(&flex);
+        assert_eq!(view, flex);
+    }
+
+    #[test]
+    fn reference() {
+        let value = 42;
+        let reference = &value;
+        let view: &i32 = Reborrow::reborrow(&reference);
+        assert!(core::ptr::eq(view, &value));
+    }
+}
+
+// Reborrowing containers
+#[cfg(feature = "alloc")]
+mod containers {
+    use super::*;
+    use flex::Flex;
+
+    fn give(s: &str) -> Flex<'static, str> {
+        Flex::Give(s.into())
+    }
+
+    #[test]
+    fn option_and_result() {
+        let some = Some(give("a"));
+        let ok: Result<_, u8> = Ok(give("b"));
+        assert!(matches!(some.reborrow(), Some(Flex::Lend("a"))));
+        assert!(matches!(ok.reborrow(), Ok(Flex::Lend("b"))));
+    }
+
+    #[test]
+    fn tuple_and_array() {
+        let tuple = (1u8, give("c"));
+        let array = [give("d"), give("e")];
+        assert!(matches!(tuple.reborrow(), (1, Flex::Lend("c"))));
+        assert!(matches!(
+            array.reborrow(),
+            [Flex::Lend("d"), Flex::Lend("e")]
+        ));
+    }
+
+    #[test]
+    fn vec() {
+        let vec = vec![give("f")];
+        let view: Vec<Flex<'_, str>> = vec.reborrow();
+        assert!(matches!(view[0], Flex::Lend(r) if core::ptr::eq(r, &*vec[0])));
+    }
+}
+
+// #[derive(Reborrow)] tests
+#[cfg(feature = "macros")]
+mod derive {
+    use super::*;
+    use flex::Flex;
+
+    #[derive(Reborrow, Debug, PartialEq)]
+    struct Message<'a> {
+        id: u32,
+        #[flex(clone)]
+        label: String,
+        name: Flex<'a, str>,
+        payload: Option<Flex<'a, [u8]>>,
+    }
+
+    #[derive(Reborrow, Debug, PartialEq)]
+    struct Pair<'a, T>(Flex<'a, str>, #[flex(clone)] T);
+
+    #[derive(Reborrow, Debug, PartialEq)]
+    struct Copied<'a, T>(Flex<'a, str>, T);
+
+    #[derive(Reborrow, Debug, PartialEq)]
+    enum Token<'a> {
+        Word(Flex<'a, str>),
+        Bytes { data: Flex<'a, [u8]>, len: usize },
+        End,
+    }
+
+    fn name_len(message: Message<'_>) -> usize {
+        message.name.len()
+    }
+
+    #[test]
+    fn named_struct() {
+        let owned: Message<'static> = Message {
+            id: 7,
+            label: String::from("label"),
+            name: Flex::Give("hello".into()),
+            payload: Some(Flex::Give(vec![1].into_boxed_slice())),
+        };
+
+        let view = owned.reborrow();
+        assert!(matches!(view.name, Flex::Lend(r) if core::ptr::eq(r, &*owned.name)));
+        assert!(matches!(view.payload, Some(Flex::Lend(&[1]))));
+        assert_eq!(view, owned);
+        assert_eq!(name_len(owned.reborrow()), 5);
+        assert_eq!(owned.id, 7);
+    }
+
+    #[test]
+    fn tuple_struct_with_generic() {
+        let owned = Pair(Flex::Give("x".into()), vec![1]);
+        let view: Pair<'_, Vec<i32>> = owned.reborrow();
+        assert!(matches!(view.0, Flex::Lend("x")));
+        assert_eq!(view.1, [1]);
+    }
+
+    #[test]
+    fn copied_generic() {
+        let owned = Copied(Flex::Give("x".into()), 3u8);
+        let view: Copied<'_, u8> = owned.reborrow();
+        assert_eq!(view, Copied(Flex::Lend("x"), 3));
+    }
+
+    #[test]
+    fn enums() {
+        let word = Token::Word(Flex::Give("word".into()));
+        let bytes = Token::Bytes {
+            data: Flex::Give(b"ab".to_vec().into_boxed_slice()),
+            len: 2,
+        };
+
+        assert!(matches!(word.reborrow(), Token::Word(Flex::Lend("word"))));
+        assert!(matches!(
+            bytes.reborrow(),
+            Token::Bytes {
+                data: Flex::Lend(b"ab"),
+                len: 2
+            }
+        ));
+        assert_eq!(Token::End.reborrow(), Token::End);
+    }
+}
@@ -0,0 +1,159 @@
+#![cfg(all(feature = "serde", feature = "alloc"))]
+
+use flex::{Flex, FlexArc};
+use serde::{Deserialize, Serialize};
+
+// Serialize trait tests
+mod serialize {
+    use super::*;
+
+    #[test]
+    fn lend_str() {
+        let flex = Flex::Lend("hello");
+        assert_eq!(serde_json::to_string(&flex).unwrap(), "\"hello\"");
+    }
+
+    #[test]
+    fn give_slice() {
+        let flex = Flex::Give(vec![1, 2, 3].into_boxed_slice());
+        assert_eq!(serde_json::to_string(&flex).unwrap(), "[1,2,3]");
+    }
+}
+
+// Deserialize trait tests
+mod deserialize {
+    use super::*;
+
+    #[test]
+    fn str_owns() {
+        let flex: Flex<str> = serde_json::from_str("\"hello\"").unwrap();
+        assert!(matches!(flex, Flex::Give(_)));
+        assert_eq!(&*flex, "hello");
+    }
+
+    #[test]
+    fn str_reader() {
+        let flex: Flex<'static, str> = serde_json::from_reader(&b"\"hello\""[..]).unwrap();
+        assert!(matches!(flex, Flex::Give(_)));
+        assert_eq!(&*flex, "hello");
+    }
+
+    #[test]
+    fn str_value() {
+        let value = serde_json::json!("hello");
+        let flex: Flex<'static, str> = serde_json::from_value(value).unwrap();
+        assert_eq!(&*flex, "hello");
+    }
+
+    #[test]
+    fn bytes_value() {
+        let value = serde_json::json!([1, 2, 3]);
+        let flex: Flex<'static, [u8]> = serde_json::from_value(value).unwrap();
+        assert_eq!(&*flex, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn owned_struct() {
+        #[derive(Deserialize)]
+        struct Config {
+            name: Flex<'static, str>,
+        }
+
+        let config: Config = serde_json::from_reader(&br#"{"name":"flex"}"#[..]).unwrap();
+        assert_eq!(&*config.name, "flex");
+    }
+
+    #[test]
+    fn bytes_from_seq() {
+        let flex: Flex<[u8]> = serde_json::from_str("[1,2,3]").unwrap();
+        assert!(matches!(flex, Flex::Give(_)));
+        assert_eq!(&*flex, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn shared_bytes_from_seq() {
+        let flex: FlexArc<[u8]> = serde_json::from_str("[4,5]").unwrap();
+        assert_eq!(&*flex, &[4, 5]);
+    }
+
+    #[test]
+    fn unsized_give() {
+        #[derive(Deserialize)]
+        struct Samples {
+            #[serde(deserialize_with = "Flex::deserialize_give")]
+            values: Flex<'static, [u32]>,
+        }
+
+        let samples: Samples = serde_json::from_str(r#"{"values":[1,2,3]}"#).unwrap();
+        assert!(matches!(samples.values, Flex::Give(_)));
+        assert_eq!(&*samples.values, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn sized_static() {
+        let flex: Flex<'static, u32> = serde_json::from_str("42").unwrap();
+        assert_eq!(*flex, 42);
+    }
+
+    #[test]
+    fn shared_str() {
+        let flex: FlexArc<str> = serde_json::from_str("\"a\\tb\"").unwrap();
+        assert_eq!(&*flex, "a\tb");
+    }
+
+    #[test]
+    fn struct_fields() {
+        #[derive(Serialize, Deserialize)]
+        struct Message<'a> {
+            #[serde(borrow, deserialize_with = "flex::serde::borrow")]
+            name: Flex<'a, str>,
+            count: Flex<'static, u32>,
+        }
+
+        let json = r#"{"name":"hello","count":7}"#;
+        let msg: Message = serde_json::from_str(json).unwrap();
+        assert!(matches!(msg.name, Flex::Lend(_)));
+        assert_eq!(*msg.count, 7);
+        assert_eq!(serde_json::to_string(&msg).unwrap(), json);
+    }
+}
+
+// Borrowing through flex::serde::borrow
+mod borrow {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Message<'a> {
+        #[serde(borrow, deserialize_with = "flex::serde::borrow")]
+        name: Flex<'a, str>,
+        #[serde(borrow, deserialize_with = "flex::serde::borrow")]
+        data: Flex<'a, [u8]>,
+    }
+
+    #[test]
+    fn lend() {
+        let json = r#"{"name":"hello","data":"abc"}"#;
+        let msg: Message = serde_json::from_str(json).unwrap();
+        assert!(matches!(msg.name, Flex::Lend("hello")));
+        assert!(matches!(msg.data, Flex::Lend(b"abc")));
+    }
+
+    #[test]
+    fn escaped_owns() {
+        let json = r#"{"name":"a\nb","data":[1,2]}"#;
+        let msg: Message = serde_json::from_str(json).unwrap();
+        assert!(matches!(msg.name, Flex::Give(_)));
+        assert_eq!(&*msg.name, "a\nb");
+        assert!(matches!(msg.data, Flex::Give(_)));
+        assert_eq!(&*msg.data, &[1, 2]);
+    }
+
+    #[test]
+    fn reader_owns() {
+        let json = br#"{"name":"hello","data":[3]}"#;
+        let mut de = serde_json::Deserializer::from_reader(&json[..]);
+        let msg: Message<'static> = Message::deserialize(&mut de).unwrap();
+        assert!(matches!(msg.name, Flex::Give(_)));
+        assert_eq!(&*msg.name, "hello");
+    }
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "alloc")]
+
+use flex::{Flex, FlexArc, FlexRc};
+use std::rc::Rc;
+use std::sync::Arc;
+
+// Clone trait tests
+mod clone {
+    use super::*;
+
+    #[test]
+    fn arc_bumps_refcount() {
+        let arc: Arc<[i32]> = Arc::from(&[1, 2, 3][..]);
+        let flex1 = FlexArc::from(arc.clone());
+        let flex2 = flex1.clone();
+        assert_eq!(Arc::strong_count(&arc), 3);
+        assert_eq!(&*flex2, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn rc_bumps_refcount() {
+        let rc: Rc<str> = Rc::from("hello");
+        let flex1 = FlexRc::from(rc.clone());
+        let flex2 = flex1.clone();
+        assert_eq!(Rc::strong_count(&rc), 3);
+        assert_eq!(&*flex2, "hello");
+    }
+
+    #[test]
+    fn lend_clone() {
+        let flex1 = FlexArc::Lend("hello");
+        let flex2 = flex1.clone();
+        assert!(matches!(flex2, FlexArc::Lend(_)));
+    }
+}
+
+// Conversion tests
+mod convert {
+    use super::*;
+    use std::fmt::Debug;
+
+    #[test]
+    fn from_flex_lend() {
+        let flex = FlexArc::from(Flex::Lend(&[1, 2, 3][..]));
+        assert!(matches!(flex, FlexArc::Lend(_)));
+    }
+
+    #[test]
+    fn from_flex_give_moves() {
+        let flex = Flex::Give(Box::new(42) as Box<dyn Debug>);
+        let shared = FlexArc::from(flex);
+        assert!(matches!(shared, FlexArc::Give(_)));
+        assert_eq!(format!("{:?}", &*shared), "42");
+    }
+
+    #[test]
+    fn into_arc_from_share() {
+        let arc: Arc<str> = Arc::from("hello");
+        let flex = FlexArc::from(arc.clone());
+        let back: Arc<str> = flex.into();
+        assert!(Arc::ptr_eq(&arc, &back));
+    }
+
+    #[test]
+    fn into_rc_from_lend() {
+        let rc: Rc<[i32]> = FlexRc::Lend(&[1, 2, 3][..]).into_rc();
+        assert_eq!(&*rc, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn claim_str() {
+        let s = String::from("hello");
+        let owned: FlexArc<'static, str> = FlexArc::from(s.as_str()).claim();
+        assert!(matches!(owned, FlexArc::Give(_)));
+        assert_eq!(&*owned, "hello");
+    }
+}
+
+// Forwarded trait tests
+mod derive {
+    use super::*;
+
+    #[test]
+    fn lend_eq_share() {
+        let lend = FlexArc::Lend(&[1, 2, 3][..]);
+        let share = FlexArc::from(Arc::<[i32]>::from(&[1, 2, 3][..]));
+        assert_eq!(lend, share);
+        assert!(share < [1, 2, 4][..]);
+    }
+
+    #[test]
+    fn display() {
+        let flex = FlexRc::from(Rc::new(42));
+        assert_eq!(format!("{}", flex), "42");
+    }
+}
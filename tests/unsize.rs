@@ -0,0 +1,72 @@
+use flex::{FlexWith, Unowned};
+
+// Unsizing through map
+mod map {
+    use super::*;
+
+    #[cfg(feature = "alloc")]
+    use core::fmt::Debug;
+    #[cfg(feature = "alloc")]
+    use flex::Flex;
+
+    #[test]
+    fn lend_slice() {
+        let array = [1u8, 2, 3];
+        let sized: FlexWith<[u8; 3], Unowned<[u8; 3]>> = FlexWith::Lend(&array);
+        let slice: FlexWith<[u8], Unowned<[u8]>> = sized.map(|r| r as &[u8], |o| o.never());
+        assert!(matches!(slice, FlexWith::Lend(r) if r.as_ptr() == array.as_ptr()));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn give_dyn() {
+        let owned = Flex::Give(Box::new(42u32));
+        let debug: Flex<dyn Debug> = owned.map(|r| r as &dyn Debug, |o| o as Box<dyn Debug>);
+        assert_eq!(format!("{:?}", debug), "Give(42)");
+    }
+}
+
+// flex_unsize! tests
+#[cfg(feature = "alloc")]
+mod macro_unsize {
+    use core::fmt::Debug;
+    use flex::{flex_unsize, Flex};
+
+    #[test]
+    fn lend_dyn() {
+        let value = 42;
+        let debug: Flex<dyn Debug> = flex_unsize!(Flex::Lend(&value));
+        assert_eq!(format!("{:?}", &*debug), "42");
+    }
+
+    #[test]
+    fn give_slice() {
+        let boxed = Box::new([7u8; 32]);
+        let ptr = boxed.as_ptr();
+        let slice: Flex<[u8]> = flex_unsize!(Flex::Give(boxed));
+        match slice {
+            Flex::Give(b) => assert_eq!((b.len(), b.as_ptr()), (32, ptr)),
+            Flex::Lend(..) => panic!("expected Give"),
+        }
+    }
+
+    #[test]
+    fn argument() {
+        fn show(flex: Flex<dyn Debug>) -> String {
+            format!("{:?}", &*flex)
+        }
+
+        let owned = Flex::Give(Box::new("hello"));
+        assert_eq!(show(flex_unsize!(owned)), "\"hello\"");
+    }
+
+    #[test]
+    fn rc() {
+        use flex::FlexRc;
+        use std::rc::Rc;
+
+        let shared: FlexRc<[u8; 2]> = FlexRc::Give(Rc::new([1, 2]));
+        let slice: FlexRc<[u8]> = flex_unsize!(shared);
+        assert_eq!(&*slice, &[1, 2]);
+    }
+}
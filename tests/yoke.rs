@@ -0,0 +1,37 @@
+#![cfg(all(feature = "yoke", feature = "alloc"))]
+
+use std::rc::Rc;
+
+use flex::Flex;
+use yoke::Yoke;
+
+// Flex as the view of a Yoke
+#[test]
+fn attach() {
+    let cart: Rc<str> = Rc::from("hello world");
+    let yoke: Yoke<Flex<'static, str>, Rc<str>> =
+        Yoke::attach_to_cart(cart.clone(), |s| Flex::Lend(&s[6..]));
+    assert!(matches!(yoke.get(), Flex::Lend("world")));
+    assert_eq!(yoke.get().as_ptr(), cart[6..].as_ptr());
+}
+
+#[test]
+fn map_project() {
+    let cart: Rc<str> = Rc::from("hello world");
+    let yoke: Yoke<Flex<'static, str>, Rc<str>> = Yoke::attach_to_cart(cart, |s| Flex::Lend(s));
+    let word: Yoke<Flex<'static, str>, Rc<str>> =
+        yoke.map_project(|flex, _| flex.map(|s| s.split(' ').next().unwrap(), |o| o));
+    assert_eq!(&**word.get(), "hello");
+}
+
+#[test]
+fn with_mut() {
+    let cart: Rc<str> = Rc::from("hello");
+    let mut yoke: Yoke<Flex<'static, str>, Rc<str>> = Yoke::attach_to_cart(cart, |s| Flex::Lend(s));
+    yoke.with_mut(|flex| {
+        if let Flex::Lend(s) = *flex {
+            *flex = Flex::Lend(&s[1..]);
+        }
+    });
+    assert_eq!(&**yoke.get(), "ello");
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "zerofrom")]
+
+use flex::Flex;
+use zerofrom::ZeroFrom;
+
+// Borrowing from another Flex
+#[test]
+fn flex() {
+    let lend = Flex::Lend("hello");
+    let copy: Flex<str> = Flex::zero_from(&lend);
+    assert!(matches!(copy, Flex::Lend("hello")));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn give() {
+    let give: Flex<[u8]> = Flex::Give(vec![1, 2, 3].into());
+    let view: Flex<[u8]> = Flex::zero_from(&give);
+    assert!(matches!(view, Flex::Lend(r) if r.as_ptr() == give.as_ptr()));
+}
+
+// Borrowing from unsized data
+#[test]
+fn slices() {
+    let text: Flex<str> = Flex::zero_from("hello");
+    assert!(matches!(text, Flex::Lend("hello")));
+
+    let bytes: Flex<[u8]> = Flex::zero_from(&b"hi"[..]);
+    assert!(matches!(bytes, Flex::Lend(b"hi")));
+}